@@ -0,0 +1,267 @@
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+//! Procedural macros for [`goober_runtime`](https://docs.rs/goober-runtime).
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Generics,
+    LitStr, Path,
+};
+
+/// Generates reactive companions for a struct with named fields, and
+/// implements `goober_runtime::Reactive` for it.
+///
+/// For a struct `Settings`, this generates:
+/// - `SettingsSignals`, with an `RwSignal` for each field, a `new()`
+///   constructor, `snapshot()` to read every signal back into a `Settings`,
+///   and `set_all()` to write a `Settings` into every signal in one batch.
+/// - `SettingsFields<T>`, with a `StoreField` for each field, created from
+///   the `StoreField` (or `Store`) holding a `Settings` with `new()`.
+///
+/// Both have the same visibility as the struct, and their fields have the
+/// same visibility as the struct's fields.
+///
+/// The runtime is assumed to be reachable at `::goober_runtime`. If it is
+/// only reachable through another crate, use
+/// `#[reactive(crate = "goober::runtime")]`.
+#[proc_macro_derive(Reactive, attributes(reactive))]
+pub fn derive_reactive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = crate_path(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`Reactive` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`Reactive` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let signals = format_ident!("{name}Signals");
+    let fields_name = format_ident!("{name}Fields");
+
+    // every value that lives in a signal has to be `'static`
+    let mut generics = input.generics.clone();
+    let type_params = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    {
+        let where_clause = generics.make_where_clause();
+        for param in &type_params {
+            where_clause.predicates.push(parse_quote!(#param: 'static));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let root = quote!(__GooberRoot);
+    let root_generics = with_root(&generics, &root);
+    let (root_impl_generics, root_ty_generics, root_where_clause) = root_generics.split_for_impl();
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().expect("named fields have idents"))
+        .collect::<Vec<_>>();
+    let names = idents
+        .iter()
+        .map(|ident| LitStr::new(&ident.to_string(), ident.span()))
+        .collect::<Vec<_>>();
+    let vises = fields.iter().map(|field| &field.vis).collect::<Vec<_>>();
+    let tys = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    // `snapshot()` is only available if every field can be cloned
+    let mut clone_generics = generics.clone();
+    clone_generics.make_where_clause().predicates.extend(
+        tys.iter()
+            .map(|ty| -> syn::WherePredicate { parse_quote!(#ty: ::core::clone::Clone) }),
+    );
+    let clone_where_clause = &clone_generics.where_clause;
+
+    let signals_doc =
+        format!("Reactive version of [`{name}`], with an `RwSignal` for each of its fields.");
+    let fields_doc = format!("Paths to each of the fields of a [`{name}`] held in a store.");
+
+    Ok(quote! {
+        #[doc = #signals_doc]
+        #[derive(Debug)]
+        #vis struct #signals #impl_generics #where_clause {
+            #(
+                #[allow(missing_docs)]
+                #vises #idents: #krate::RwSignal<#tys>,
+            )*
+        }
+
+        impl #impl_generics ::core::clone::Clone for #signals #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics ::core::marker::Copy for #signals #ty_generics #where_clause {}
+
+        impl #impl_generics ::core::cmp::PartialEq for #signals #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                true #(&& self.#idents == other.#idents)*
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #signals #ty_generics #where_clause {}
+
+        impl #impl_generics #signals #ty_generics #where_clause {
+            /// Creates a signal for each of the fields of `value`.
+            #[track_caller]
+            #vis fn new(value: #name #ty_generics) -> Self {
+                Self {
+                    #(#idents: #krate::create_rw_signal(value.#idents),)*
+                }
+            }
+
+            /// Sets every signal to the matching field of `value`, notifying
+            /// subscribers once all of them have been set.
+            #[track_caller]
+            #vis fn set_all(&self, value: #name #ty_generics) {
+                #krate::batch(|| {
+                    #(#krate::SignalSet::set(&self.#idents, value.#idents);)*
+                });
+            }
+        }
+
+        impl #impl_generics #signals #ty_generics #clone_where_clause {
+            /// Clones the current value of every signal, subscribing the
+            /// running effect to all of them.
+            #[track_caller]
+            #vis fn snapshot(&self) -> #name #ty_generics {
+                #name {
+                    #(
+                        #idents: #krate::SignalWith::with(
+                            &self.#idents,
+                            ::core::clone::Clone::clone,
+                        ),
+                    )*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics>
+            for #signals #ty_generics #where_clause
+        {
+            #[track_caller]
+            fn from(value: #name #ty_generics) -> Self {
+                Self::new(value)
+            }
+        }
+
+        #[doc = #fields_doc]
+        #[derive(Debug)]
+        #vis struct #fields_name #root_impl_generics #root_where_clause {
+            #(
+                #[allow(missing_docs)]
+                #vises #idents: #krate::StoreField<#root, #tys>,
+            )*
+        }
+
+        impl #root_impl_generics ::core::clone::Clone
+            for #fields_name #root_ty_generics #root_where_clause
+        {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #root_impl_generics ::core::marker::Copy
+            for #fields_name #root_ty_generics #root_where_clause
+        {
+        }
+
+        impl #root_impl_generics #fields_name #root_ty_generics #root_where_clause {
+            /// Creates a path to each of the fields of the value at `field`.
+            #[track_caller]
+            #vis fn new(field: #krate::StoreField<#root, #name #ty_generics>) -> Self {
+                Self {
+                    #(
+                        #idents: field.field(
+                            #names,
+                            |value| &value.#idents,
+                            |value| &mut value.#idents,
+                        ),
+                    )*
+                }
+            }
+        }
+
+        impl #impl_generics #krate::Reactive for #name #ty_generics #where_clause {
+            type Signals = #signals #ty_generics;
+            type Fields<#root: 'static> = #fields_name #root_ty_generics;
+
+            #[track_caller]
+            fn into_signals(self) -> Self::Signals {
+                #signals::new(self)
+            }
+
+            #[track_caller]
+            fn fields<#root: 'static>(
+                field: #krate::StoreField<#root, Self>,
+            ) -> Self::Fields<#root> {
+                #fields_name::new(field)
+            }
+        }
+    })
+}
+
+/// Adds the type parameter for the root of the store to `generics`.
+fn with_root(generics: &Generics, root: &TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    let param: GenericParam = parse_quote!(#root: 'static);
+    // type parameters have to come after lifetimes
+    let index = generics
+        .params
+        .iter()
+        .take_while(|param| matches!(param, GenericParam::Lifetime(_)))
+        .count();
+    generics.params.insert(index, param);
+    generics
+}
+
+/// Reads the path to the runtime from `#[reactive(crate = "...")]`.
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut path = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("reactive") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                let value: LitStr = meta.value()?.parse()?;
+                path = Some(value.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown `reactive` attribute, expected `crate`"))
+            }
+        })?;
+    }
+    Ok(path.unwrap_or_else(|| {
+        let ident = syn::Ident::new("goober_runtime", Span::call_site());
+        parse_quote!(::#ident)
+    }))
+}
@@ -3,8 +3,8 @@ use taffy::prelude::*;
 
 use glutin::surface::GlSurface;
 use goober_runtime::{
-    as_child_of_current_owner, create_effect, create_render_effect, create_runtime, create_trigger,
-    store_value, with_owner, Owner,
+    as_child_of_current_owner, create_effect_with_options, create_render_effect, create_runtime,
+    create_trigger, store_value, with_owner, EffectOptions, EffectPhase, Owner,
 };
 use goober_ui::{skia_safe::Color, *};
 
@@ -37,7 +37,7 @@ impl From<std::io::Error> for Error {
 }
 
 pub fn launch<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(), Error> {
-    let _rt = create_runtime();
+    let rt = create_runtime();
     let (root, _disposer) = as_child_of_current_owner(|()| Rc::new(make()))(());
 
     let event_loop = EventLoop::new()?;
@@ -67,28 +67,34 @@ pub fn launch<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(), E
         (node, store_value(tf))
     };
 
-    create_effect({
-        let root = root.clone();
-        move |_| {
-            taffy.update_value(|tf| {
-                root.measure(Some(node), tf);
-                if tf.dirty(node).unwrap() {
-                    ren.with_value(|ren| {
-                        let size = ren.window.inner_size();
-                        tf.compute_layout(
-                            node,
-                            Size {
-                                width: AvailableSpace::Definite(size.width as f32),
-                                height: AvailableSpace::Definite(size.height as f32),
-                            },
-                        )
-                        .unwrap();
-                        render_trigger.notify();
-                    });
-                }
-            });
-        }
-    });
+    create_effect_with_options(
+        {
+            let root = root.clone();
+            move |_| {
+                taffy.update_value(|tf| {
+                    root.measure(Some(node), tf);
+                    if tf.dirty(node).unwrap() {
+                        ren.with_value(|ren| {
+                            let size = ren.window.inner_size();
+                            tf.compute_layout(
+                                node,
+                                Size {
+                                    width: AvailableSpace::Definite(size.width as f32),
+                                    height: AvailableSpace::Definite(size.height as f32),
+                                },
+                            )
+                            .unwrap();
+                            render_trigger.notify();
+                        });
+                    }
+                });
+            }
+        },
+        EffectOptions {
+            phase: EffectPhase::Layout,
+            priority: 0,
+        },
+    );
 
     create_render_effect({
         let root = root.clone();
@@ -119,6 +125,20 @@ pub fn launch<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(), E
         }
     });
 
+    // layout and rendering happen once per frame, after every event of the
+    // frame has been handled
+    rt.set_phase_deferred(EffectPhase::Layout, true);
+    rt.set_phase_deferred(EffectPhase::Render, true);
+
+    // lets other threads wake the event loop to apply what they wrote to
+    // signals through a `SendSignal`
+    rt.set_sync_waker({
+        let proxy = event_loop.create_proxy();
+        move || {
+            _ = proxy.send_event(());
+        }
+    });
+
     event_loop
         .run({
             let owner = Owner::current().expect("owner exploded");
@@ -131,10 +151,27 @@ pub fn launch<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(), E
                     event: WindowEvent::CloseRequested,
                     ..
                 } => explode.exit(),
+                Event::UserEvent(()) => with_owner(owner, || {
+                    rt.run_sync_writes();
+                }),
+                // run any timers (such as resources polling) that came due
+                // while handling events, and sleep until the next one
+                Event::AboutToWait => with_owner(owner, || {
+                    let next_timer = rt.run_timers();
+                    rt.run_phase(EffectPhase::Layout);
+                    rt.run_phase(EffectPhase::Render);
+                    explode.set_control_flow(match next_timer {
+                        Some(next) => ControlFlow::WaitUntil(next),
+                        None => ControlFlow::Wait,
+                    })
+                }),
                 Event::WindowEvent {
                     event: WindowEvent::RedrawRequested,
                     ..
-                } => with_owner(owner, || render_trigger.notify()),
+                } => with_owner(owner, || {
+                    render_trigger.notify();
+                    rt.run_phase(EffectPhase::Render);
+                }),
                 Event::WindowEvent {
                     event: WindowEvent::Resized(size),
                     ..
@@ -230,7 +267,7 @@ pub fn launch_terminal<V: View + 'static>(make: impl Fn() -> V + 'static) -> Res
 fn launch_term<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(), Error> {
     use std::io::stdout;
 
-    let _rt = create_runtime();
+    let rt = create_runtime();
     let owner = Owner::current().expect("owner exploded");
     let (root, _disposer) = as_child_of_current_owner(|()| Rc::new(make()))(());
 
@@ -255,26 +292,32 @@ fn launch_term<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(),
         (node, store_value(tf))
     };
 
-    create_effect({
-        let root = root.clone();
-        move |_| {
-            taffy.update_value(|tf| {
-                with_owner(owner, || root.measure_terminal(None, tf));
-                if tf.dirty(node).unwrap() {
-                    let (width, height) = crossterm::terminal::size().unwrap();
-                    tf.compute_layout(
-                        node,
-                        Size {
-                            width: AvailableSpace::Definite(width as f32),
-                            height: AvailableSpace::Definite(height as f32),
-                        },
-                    )
-                    .unwrap();
-                    render_trigger.notify();
-                }
-            });
-        }
-    });
+    create_effect_with_options(
+        {
+            let root = root.clone();
+            move |_| {
+                taffy.update_value(|tf| {
+                    with_owner(owner, || root.measure_terminal(None, tf));
+                    if tf.dirty(node).unwrap() {
+                        let (width, height) = crossterm::terminal::size().unwrap();
+                        tf.compute_layout(
+                            node,
+                            Size {
+                                width: AvailableSpace::Definite(width as f32),
+                                height: AvailableSpace::Definite(height as f32),
+                            },
+                        )
+                        .unwrap();
+                        render_trigger.notify();
+                    }
+                });
+            }
+        },
+        EffectOptions {
+            phase: EffectPhase::Layout,
+            priority: 0,
+        },
+    );
 
     create_render_effect({
         let root = root.clone();
@@ -305,30 +348,71 @@ fn launch_term<V: View + 'static>(make: impl Fn() -> V + 'static) -> Result<(),
         event::{DisableMouseCapture, EnableMouseCapture},
         terminal::{Clear, ClearType},
     };
-    use goober_runtime::{create_signal_from_stream, SignalWith};
-    let stream = create_signal_from_stream(crossterm::event::EventStream::new());
-
-    create_effect(move |_| {
-        let event = stream.with(|x| x.as_ref().map(|x| x.as_ref().unwrap().clone()))?;
-        println!("{event:?}");
-        taffy.update_value(|taffy| {
-            with_owner(owner, || {
-                root.ev(
-                    &goober_ui::Event::Terminal(event.clone()),
-                    &RenderContext {
-                        taffy,
-                        layout: *taffy.layout(node).unwrap(),
-                        this_node: node,
-                        density,
-                        is_terminal: true,
-                    },
-                );
-            })
-        });
-        Some(())
+    use std::{
+        sync::mpsc::{channel, RecvTimeoutError},
+        time::Instant,
+    };
+
+    enum Wake {
+        Terminal(std::io::Result<crossterm::event::Event>),
+        SyncWrites,
+    }
+
+    // terminal events are read on their own thread, so that the loop below
+    // can also be woken by timers and by other threads writing to signals
+    // through a `SendSignal`
+    let (wake, woken) = channel();
+    rt.set_sync_waker({
+        let wake = wake.clone();
+        move || {
+            _ = wake.send(Wake::SyncWrites);
+        }
+    });
+    std::thread::spawn(move || loop {
+        let event = crossterm::event::read();
+        let failed = event.is_err();
+        if wake.send(Wake::Terminal(event)).is_err() || failed {
+            break;
+        }
     });
 
+    let mut next_timer = rt.run_timers();
+    let result = loop {
+        let message = match next_timer {
+            Some(next) => woken.recv_timeout(next.saturating_duration_since(Instant::now())),
+            None => woken.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match message {
+            Ok(Wake::Terminal(Err(error))) => break Err(error.into()),
+            Ok(Wake::Terminal(Ok(event))) => {
+                println!("{event:?}");
+                taffy.update_value(|taffy| {
+                    with_owner(owner, || {
+                        root.ev(
+                            &goober_ui::Event::Terminal(event),
+                            &RenderContext {
+                                taffy,
+                                layout: *taffy.layout(node).unwrap(),
+                                this_node: node,
+                                density,
+                                is_terminal: true,
+                            },
+                        );
+                    })
+                });
+            }
+            Ok(Wake::SyncWrites) => with_owner(owner, || {
+                rt.run_sync_writes();
+            }),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break Ok(()),
+        }
+        // run any timers (such as resources polling) that came due, and
+        // wait until the next one
+        next_timer = with_owner(owner, || rt.run_timers());
+    };
+
     ren.update_value(|ren| ren.execute(DisableMouseCapture).unwrap());
 
-    Ok(())
+    result
 }
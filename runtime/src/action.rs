@@ -0,0 +1,464 @@
+use crate::{
+    batch, create_rw_signal, expect_context, on_cleanup, spawn::spawn_local, store_value,
+    with_owner, Owner, QueryClient, ReadSignal, RwSignal, SignalSet, SignalUpdate, StoredValue,
+    ToQueryKey,
+};
+use futures::future::{abortable, AbortHandle};
+use std::{cell::RefCell, fmt, future::Future, pin::Pin, rc::Rc};
+
+type ActionFn<I, O> = Rc<dyn Fn(&I) -> Pin<Box<dyn Future<Output = O>>>>;
+type Hooks<O> = Rc<RefCell<Vec<Rc<dyn Fn(&O)>>>>;
+
+/// Creates an [`Action`], which runs an async function whenever it is
+/// dispatched, and tracks its state reactively.
+///
+/// Where a [`Resource`](crate::Resource) reads async data whenever its source
+/// changes, an action writes it when asked to, such as when a form is
+/// submitted.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// # let tokio = tokio::runtime::Runtime::new().unwrap();
+/// # let _guard = tokio.enter();
+/// async fn save_todo(title: String) -> Result<usize, String> {
+///     // pretend this is sent to a server
+///     Ok(title.len())
+/// }
+///
+/// let save = create_action(|title: &String| save_todo(title.clone()));
+/// assert_eq!(save.value().get(), None);
+///
+/// save.dispatch("write docs".to_string());
+/// assert_eq!(save.value().get(), Some(Ok(10)));
+/// assert_eq!(save.version().get(), 1);
+/// assert!(!save.pending().get());
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_action<I, O, Fu>(action_fn: impl Fn(&I) -> Fu + 'static) -> Action<I, O>
+where
+    I: 'static,
+    O: 'static,
+    Fu: Future<Output = O> + 'static,
+{
+    Action::new(action_fn)
+}
+
+/// Creates a [`MultiAction`], which runs an async function whenever it is
+/// dispatched, and tracks each dispatch as a separate [`Submission`].
+#[track_caller]
+pub fn create_multi_action<I, O, Fu>(action_fn: impl Fn(&I) -> Fu + 'static) -> MultiAction<I, O>
+where
+    I: 'static,
+    O: 'static,
+    Fu: Future<Output = O> + 'static,
+{
+    MultiAction::new(action_fn)
+}
+
+/// An async function that runs whenever it is dispatched, created with
+/// [`create_action`].
+///
+/// Only the most recent dispatch matters: dispatching again while a call is
+/// still running cancels that call, which then never updates
+/// [`value`](Action::value) or runs any hooks. If the calls should run side
+/// by side instead, use a [`MultiAction`].
+pub struct Action<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    state: StoredValue<ActionState<I, O>>,
+}
+
+struct ActionState<I: 'static, O: 'static> {
+    action_fn: ActionFn<I, O>,
+    input: RwSignal<Option<I>>,
+    value: RwSignal<Option<O>>,
+    pending: RwSignal<bool>,
+    version: RwSignal<usize>,
+    in_flight: Rc<RefCell<Option<AbortHandle>>>,
+    hooks: Hooks<O>,
+}
+
+impl<I, O> Action<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    /// Creates an [`Action`] for the given async function.
+    #[track_caller]
+    pub fn new<Fu>(action_fn: impl Fn(&I) -> Fu + 'static) -> Self
+    where
+        Fu: Future<Output = O> + 'static,
+    {
+        let in_flight = Rc::new(RefCell::new(None::<AbortHandle>));
+        // nothing is left to write the result to once the owner is gone
+        on_cleanup({
+            let in_flight = Rc::clone(&in_flight);
+            move || {
+                if let Some(handle) = in_flight.take() {
+                    handle.abort();
+                }
+            }
+        });
+
+        let state = store_value(ActionState {
+            action_fn: Rc::new(move |input| Box::pin(action_fn(input))),
+            input: create_rw_signal(None),
+            value: create_rw_signal(None),
+            pending: create_rw_signal(false),
+            version: create_rw_signal(0),
+            in_flight,
+            hooks: Default::default(),
+        });
+        Self { state }
+    }
+
+    /// Calls the async function with `input`, cancelling the previous call
+    /// if it is still running.
+    #[track_caller]
+    pub fn dispatch(&self, input: I) {
+        let (fut, signals, in_flight, hooks) = self.state.with_value(|state| {
+            let fut = (state.action_fn)(&input);
+            (
+                fut,
+                (state.input, state.value, state.pending, state.version),
+                Rc::clone(&state.in_flight),
+                Rc::clone(&state.hooks),
+            )
+        });
+        let (input_signal, value, pending, version) = signals;
+
+        let (fut, handle) = abortable(fut);
+        if let Some(previous) = in_flight.replace(Some(handle)) {
+            previous.abort();
+        }
+        batch(|| {
+            input_signal.set(Some(input));
+            pending.set(true);
+        });
+
+        spawn_local(async move {
+            // cancelled by a newer dispatch, or by the owner being disposed
+            let Ok(output) = fut.await else {
+                return;
+            };
+            in_flight.take();
+            run_hooks(&hooks, &output);
+            batch(|| {
+                value.try_set(Some(output));
+                input_signal.try_set(None);
+                pending.try_set(false);
+                version.try_update(|version| *version += 1);
+            });
+        });
+    }
+
+    /// The input of the call that is currently running, if there is one.
+    pub fn input(&self) -> RwSignal<Option<I>> {
+        self.state.with_value(|state| state.input)
+    }
+
+    /// The output of the most recent call that finished, if there is one.
+    pub fn value(&self) -> RwSignal<Option<O>> {
+        self.state.with_value(|state| state.value)
+    }
+
+    /// Whether a call is currently running.
+    pub fn pending(&self) -> ReadSignal<bool> {
+        self.state.with_value(|state| state.pending.read_only())
+    }
+
+    /// How many calls have finished. This is useful as the source of a
+    /// [`Resource`](crate::Resource) that should be refetched after every
+    /// call.
+    pub fn version(&self) -> RwSignal<usize> {
+        self.state.with_value(|state| state.version)
+    }
+
+    /// Calls `f` with the output of every call that finishes, before it is
+    /// stored in [`value`](Action::value).
+    pub fn on_resolved(&self, f: impl Fn(&O) + 'static) {
+        self.state
+            .with_value(|state| state.hooks.borrow_mut().push(Rc::new(f)));
+    }
+}
+
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Calls `f` with the output of every call that finishes with `Ok(_)`.
+    ///
+    /// This is where resources that depend on what the action changed
+    /// should be [refetched](crate::Resource::refetch).
+    pub fn on_success(&self, f: impl Fn(&T) + 'static) {
+        self.on_resolved(move |output| {
+            if let Ok(output) = output {
+                f(output)
+            }
+        });
+    }
+
+    /// Invalidates the queries whose key starts with `prefix` in the
+    /// [`QueryClient`] provided in the current context, after every call
+    /// that finishes with `Ok(_)`.
+    ///
+    /// # Panics
+    /// Panics if no [`QueryClient`] has been provided.
+    #[track_caller]
+    pub fn invalidate_on_success(&self, prefix: impl ToQueryKey) {
+        let client = expect_context::<QueryClient>();
+        let prefix = prefix.to_query_key();
+        self.on_success(move |_| client.invalidate(&prefix));
+    }
+}
+
+/// An async function that runs whenever it is dispatched, keeping track of
+/// every dispatch separately, created with [`create_multi_action`].
+///
+/// Unlike an [`Action`], dispatching again does not cancel the calls that
+/// are still running, so it suits things like adding several items in a
+/// row. Each call can be followed and cancelled through its [`Submission`].
+pub struct MultiAction<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    state: StoredValue<MultiActionState<I, O>>,
+}
+
+struct MultiActionState<I: 'static, O: 'static> {
+    owner: Option<Owner>,
+    action_fn: ActionFn<I, O>,
+    submissions: RwSignal<Vec<Submission<I, O>>>,
+    version: RwSignal<usize>,
+    hooks: Hooks<O>,
+}
+
+/// One call of a [`MultiAction`].
+pub struct Submission<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    /// The input the call was made with.
+    pub input: RwSignal<Option<I>>,
+    /// The output of the call, once it has finished.
+    pub value: RwSignal<Option<O>>,
+    pending: RwSignal<bool>,
+    canceled: RwSignal<bool>,
+    handle: StoredValue<Option<AbortHandle>>,
+}
+
+impl<I, O> MultiAction<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    /// Creates a [`MultiAction`] for the given async function.
+    #[track_caller]
+    pub fn new<Fu>(action_fn: impl Fn(&I) -> Fu + 'static) -> Self
+    where
+        Fu: Future<Output = O> + 'static,
+    {
+        let state = store_value(MultiActionState {
+            owner: Owner::current(),
+            action_fn: Rc::new(move |input| Box::pin(action_fn(input))),
+            submissions: create_rw_signal(Vec::new()),
+            version: create_rw_signal(0),
+            hooks: Default::default(),
+        });
+        Self { state }
+    }
+
+    /// Calls the async function with `input`, adding a new [`Submission`]
+    /// to [`submissions`](MultiAction::submissions).
+    #[track_caller]
+    pub fn dispatch(&self, input: I) {
+        let (owner, fut, submissions, version, hooks) = self.state.with_value(|state| {
+            (
+                state.owner,
+                (state.action_fn)(&input),
+                state.submissions,
+                state.version,
+                Rc::clone(&state.hooks),
+            )
+        });
+
+        let (fut, handle) = abortable(fut);
+        // submissions live as long as the action, not as long as whatever
+        // dispatched them
+        let create = || Submission {
+            input: create_rw_signal(Some(input)),
+            value: create_rw_signal(None),
+            pending: create_rw_signal(true),
+            canceled: create_rw_signal(false),
+            handle: store_value(Some(handle)),
+        };
+        let submission = match owner {
+            Some(owner) => with_owner(owner, create),
+            None => create(),
+        };
+        submissions.update(|submissions| submissions.push(submission));
+
+        spawn_local(async move {
+            let Ok(output) = fut.await else {
+                return;
+            };
+            submission.handle.try_update_value(|handle| handle.take());
+            run_hooks(&hooks, &output);
+            batch(|| {
+                submission.value.try_set(Some(output));
+                submission.input.try_set(None);
+                submission.pending.try_set(false);
+                version.try_update(|version| *version += 1);
+            });
+        });
+    }
+
+    /// Every call made so far, in the order they were dispatched.
+    pub fn submissions(&self) -> ReadSignal<Vec<Submission<I, O>>> {
+        self.state.with_value(|state| state.submissions.read_only())
+    }
+
+    /// How many calls have finished.
+    pub fn version(&self) -> RwSignal<usize> {
+        self.state.with_value(|state| state.version)
+    }
+
+    /// Calls `f` with the output of every call that finishes, before it is
+    /// stored in the [`value`](Submission::value) of its submission.
+    pub fn on_resolved(&self, f: impl Fn(&O) + 'static) {
+        self.state
+            .with_value(|state| state.hooks.borrow_mut().push(Rc::new(f)));
+    }
+}
+
+impl<I, T, E> MultiAction<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Calls `f` with the output of every call that finishes with `Ok(_)`.
+    pub fn on_success(&self, f: impl Fn(&T) + 'static) {
+        self.on_resolved(move |output| {
+            if let Ok(output) = output {
+                f(output)
+            }
+        });
+    }
+
+    /// Invalidates the queries whose key starts with `prefix` in the
+    /// [`QueryClient`] provided in the current context, after every call
+    /// that finishes with `Ok(_)`.
+    ///
+    /// # Panics
+    /// Panics if no [`QueryClient`] has been provided.
+    #[track_caller]
+    pub fn invalidate_on_success(&self, prefix: impl ToQueryKey) {
+        let client = expect_context::<QueryClient>();
+        let prefix = prefix.to_query_key();
+        self.on_success(move |_| client.invalidate(&prefix));
+    }
+}
+
+impl<I, O> Submission<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    /// Whether the call is still running.
+    pub fn pending(&self) -> ReadSignal<bool> {
+        self.pending.read_only()
+    }
+
+    /// Whether the call was cancelled with [`cancel`](Submission::cancel).
+    pub fn canceled(&self) -> ReadSignal<bool> {
+        self.canceled.read_only()
+    }
+
+    /// Cancels the call if it is still running, so that it never sets its
+    /// [`value`](Submission::value) or runs any hooks.
+    pub fn cancel(&self) {
+        if let Some(Some(handle)) = self.handle.try_update_value(|handle| handle.take()) {
+            handle.abort();
+            batch(|| {
+                self.canceled.set(true);
+                self.pending.set(false);
+            });
+        }
+    }
+}
+
+fn run_hooks<O>(hooks: &Hooks<O>, output: &O) {
+    // hooks may register other hooks
+    let hooks = hooks.borrow().clone();
+    for hook in hooks {
+        hook(output);
+    }
+}
+
+impl<I, O> Clone for Action<I, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, O> Copy for Action<I, O> {}
+
+impl<I, O> fmt::Debug for Action<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Action")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<I, O> Clone for MultiAction<I, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, O> Copy for MultiAction<I, O> {}
+
+impl<I, O> fmt::Debug for MultiAction<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiAction")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<I, O> Clone for Submission<I, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I, O> Copy for Submission<I, O> {}
+
+impl<I, O> PartialEq for Submission<I, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input && self.value == other.value
+    }
+}
+
+impl<I, O> Eq for Submission<I, O> {}
+
+impl<I, O> fmt::Debug for Submission<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Submission")
+            .field("input", &self.input)
+            .field("value", &self.value)
+            .field("pending", &self.pending)
+            .field("canceled", &self.canceled)
+            .finish()
+    }
+}
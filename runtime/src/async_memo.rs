@@ -0,0 +1,200 @@
+use crate::{
+    batch, create_isomorphic_effect, create_signal, on_cleanup, spawn::spawn_local, untrack,
+    ReadSignal, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalWith, SignalWithUntracked,
+    WriteSignal,
+};
+use futures::{
+    future::{abortable, AbortHandle},
+    task::noop_waker_ref,
+};
+use std::{
+    cell::RefCell,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+/// Creates an [`AsyncMemo`], a value derived by an async function.
+///
+/// The signals read by `f`, and by the future it returns up to its first
+/// `.await`, are tracked, and the future is run again whenever they change.
+/// Anything read after that is not tracked. While the new value is being
+/// computed, the memo keeps the previous one, and a result that has been
+/// overtaken by a newer run is dropped.
+///
+/// Unlike a [`Resource`](crate::Resource), this needs no separate `source`,
+/// and the value doesn't need to be
+/// [`Serializable`](crate::Serializable), so it suits data that only the UI
+/// uses.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// # let tokio = tokio::runtime::Runtime::new().unwrap();
+/// # let _guard = tokio.enter();
+/// let (name, set_name) = create_signal("world");
+/// let greeting = create_async_memo(move || {
+///     let name = name.get();
+///     async move { format!("hello, {name}!") }
+/// });
+/// assert_eq!(greeting.get(), Some("hello, world!".to_string()));
+///
+/// set_name.set("async");
+/// assert_eq!(greeting.get(), Some("hello, async!".to_string()));
+/// assert!(!greeting.loading().get());
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_async_memo<T, Fu>(f: impl Fn() -> Fu + 'static) -> AsyncMemo<T>
+where
+    T: PartialEq + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let (value, set_value) = create_signal(None);
+    let (loading, set_loading) = create_signal(false);
+    let in_flight = Rc::new(RefCell::new(None::<AbortHandle>));
+
+    on_cleanup({
+        let in_flight = Rc::clone(&in_flight);
+        move || {
+            if let Some(handle) = in_flight.take() {
+                handle.abort();
+            }
+        }
+    });
+
+    create_isomorphic_effect(move |_| {
+        if let Some(previous) = in_flight.take() {
+            previous.abort();
+        }
+
+        // the first poll runs in the effect, so it tracks what is read
+        // before the first `.await`
+        let mut fut = Box::pin(f());
+        let mut cx = Context::from_waker(noop_waker_ref());
+        if let Poll::Ready(new) = fut.as_mut().poll(&mut cx) {
+            batch(|| {
+                set(value, set_value, new);
+                set_loading.set(false);
+            });
+            return;
+        }
+
+        set_loading.set(true);
+        let (fut, handle) = abortable(Untracked(fut));
+        *in_flight.borrow_mut() = Some(handle);
+        let in_flight = Rc::clone(&in_flight);
+        spawn_local(async move {
+            // overtaken by a newer run, or the owner was disposed
+            let Ok(new) = fut.await else {
+                return;
+            };
+            in_flight.take();
+            batch(|| {
+                set(value, set_value, new);
+                set_loading.try_set(false);
+            });
+        });
+    });
+
+    AsyncMemo { value, loading }
+}
+
+/// Only notifies the memo's subscribers if the value has changed.
+fn set<T: PartialEq>(value: ReadSignal<Option<T>>, set_value: WriteSignal<Option<T>>, new: T) {
+    let changed = value
+        .try_with_untracked(|value| value.as_ref() != Some(&new))
+        .unwrap_or(false);
+    if changed {
+        set_value.try_set(Some(new));
+    }
+}
+
+/// Polls a future without tracking what it reads, such as the rest of an
+/// async memo's future after its first poll.
+pub(crate) struct Untracked<Fu>(pub(crate) Pin<Box<Fu>>);
+
+impl<Fu: Future> Future for Untracked<Fu> {
+    type Output = Fu::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        untrack(|| self.0.as_mut().poll(cx))
+    }
+}
+
+/// A value derived by an async function, created with
+/// [`create_async_memo`].
+///
+/// It is `None` until the function has finished for the first time.
+pub struct AsyncMemo<T: 'static> {
+    value: ReadSignal<Option<T>>,
+    loading: ReadSignal<bool>,
+}
+
+impl<T> AsyncMemo<T> {
+    /// Whether the value is being computed again.
+    pub fn loading(&self) -> Signal<bool> {
+        self.loading.into()
+    }
+}
+
+impl<T> Clone for AsyncMemo<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AsyncMemo<T> {}
+
+impl<T> SignalWith for AsyncMemo<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        self.value.with(f)
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        SignalWith::try_with(&self.value, f)
+    }
+}
+
+impl<T> SignalWithUntracked for AsyncMemo<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        self.value.with_untracked(f)
+    }
+
+    fn try_with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.value.try_with_untracked(f)
+    }
+}
+
+impl<T: Clone> SignalGet for AsyncMemo<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get(&self) -> Option<T> {
+        self.value.get()
+    }
+
+    fn try_get(&self) -> Option<Option<T>> {
+        self.value.try_get()
+    }
+}
+
+impl<T: Clone> SignalGetUntracked for AsyncMemo<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get_untracked(&self) -> Option<T> {
+        self.value.get_untracked()
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<T>> {
+        self.value.try_get_untracked()
+    }
+}
@@ -0,0 +1,353 @@
+use crate::{
+    batch, create_isomorphic_effect, create_signal, on_cleanup,
+    send_signal::{create_sync_handler, SendSignal},
+    ReadSignal, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+    SignalWithUntracked,
+};
+use std::{collections::VecDeque, future::Future, sync::Arc};
+use tokio::sync::{broadcast, mpsc, watch, Notify};
+
+/// How a [`ChannelSignal`] keeps the items it receives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChannelOptions {
+    /// How many of the most recent items to keep in
+    /// [`ChannelSignal::recent`]. Defaults to none.
+    pub buffer: usize,
+}
+
+/// The items received from a Tokio channel, created with
+/// [`create_signal_from_watch`], [`create_signal_from_mpsc`] or
+/// [`create_signal_from_broadcast`].
+///
+/// Its value is the latest item, or `None` if there hasn't been one yet.
+///
+/// The channel is read on a Tokio task, which hands what it has received to
+/// the runtime as a [`SendSignal`] write, so the items show up once whatever
+/// drives the runtime [runs](crate::RuntimeId::run_sync_writes) them. The
+/// task doesn't receive anything more until they have been run, so a
+/// bounded channel fills up, and its senders wait, if the runtime falls
+/// behind. The task is stopped when the current owner is cleaned up.
+pub struct ChannelSignal<T: 'static> {
+    latest: ReadSignal<Option<T>>,
+    recent: ReadSignal<VecDeque<T>>,
+    closed: ReadSignal<bool>,
+    lagged: ReadSignal<u64>,
+}
+
+/// What the task reading a channel has received since it last handed
+/// anything over.
+struct Received<T> {
+    items: Vec<T>,
+    lagged: u64,
+    closed: bool,
+}
+
+impl<T> Default for Received<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            lagged: 0,
+            closed: false,
+        }
+    }
+}
+
+/// The task reading a channel, and the way it hands over what it received.
+struct Forwarder<T> {
+    sender: SendSignal<Received<T>>,
+    /// Notified once the runtime has applied what was handed over.
+    applied: Arc<Notify>,
+}
+
+impl<T: Send + 'static> Forwarder<T> {
+    /// Hands over `received`, and waits until the runtime has applied it.
+    async fn send(&self, received: Received<T>) {
+        self.sender.set(received);
+        self.applied.notified().await;
+    }
+}
+
+/// Creates a [`ChannelSignal`] that follows a `watch` channel, starting with
+/// its current value.
+///
+/// # Panics
+/// Panics if called outside a Tokio runtime.
+#[track_caller]
+pub fn create_signal_from_watch<T>(
+    mut rx: watch::Receiver<T>,
+    options: ChannelOptions,
+) -> ChannelSignal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let initial = rx.borrow_and_update().clone();
+    forward(Some(initial), options, |forwarder| async move {
+        while rx.changed().await.is_ok() {
+            let value = rx.borrow_and_update().clone();
+            forwarder
+                .send(Received {
+                    items: vec![value],
+                    ..Default::default()
+                })
+                .await;
+        }
+        forwarder
+            .send(Received {
+                closed: true,
+                ..Default::default()
+            })
+            .await;
+    })
+}
+
+/// Creates a [`ChannelSignal`] with the items received from an `mpsc`
+/// channel.
+///
+/// # Panics
+/// Panics if called outside a Tokio runtime.
+#[track_caller]
+pub fn create_signal_from_mpsc<T>(
+    mut rx: mpsc::Receiver<T>,
+    options: ChannelOptions,
+) -> ChannelSignal<T>
+where
+    T: Clone + Send + 'static,
+{
+    forward(None, options, |forwarder| async move {
+        loop {
+            let mut received = Received::default();
+            match rx.recv().await {
+                Some(item) => received.items.push(item),
+                None => received.closed = true,
+            }
+            // everything that is already waiting goes in the same batch
+            while !received.closed {
+                match rx.try_recv() {
+                    Ok(item) => received.items.push(item),
+                    Err(mpsc::error::TryRecvError::Empty) => break,
+                    Err(mpsc::error::TryRecvError::Disconnected) => received.closed = true,
+                }
+            }
+            let closed = received.closed;
+            forwarder.send(received).await;
+            if closed {
+                break;
+            }
+        }
+    })
+}
+
+/// Creates a [`ChannelSignal`] with the items received from a `broadcast`
+/// channel.
+///
+/// Items that were overwritten before they could be received are counted
+/// by [`ChannelSignal::lagged`].
+///
+/// # Panics
+/// Panics if called outside a Tokio runtime.
+#[track_caller]
+pub fn create_signal_from_broadcast<T>(
+    mut rx: broadcast::Receiver<T>,
+    options: ChannelOptions,
+) -> ChannelSignal<T>
+where
+    T: Clone + Send + 'static,
+{
+    forward(None, options, |forwarder| async move {
+        loop {
+            let mut received = Received::default();
+            match rx.recv().await {
+                Ok(item) => received.items.push(item),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => received.lagged += skipped,
+                Err(broadcast::error::RecvError::Closed) => received.closed = true,
+            }
+            while !received.closed {
+                match rx.try_recv() {
+                    Ok(item) => received.items.push(item),
+                    Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                        received.lagged += skipped
+                    }
+                    Err(broadcast::error::TryRecvError::Empty) => break,
+                    Err(broadcast::error::TryRecvError::Closed) => received.closed = true,
+                }
+            }
+            let closed = received.closed;
+            forwarder.send(received).await;
+            if closed {
+                break;
+            }
+        }
+    })
+}
+
+#[track_caller]
+fn forward<T, Fu>(
+    initial: Option<T>,
+    options: ChannelOptions,
+    task: impl FnOnce(Forwarder<T>) -> Fu,
+) -> ChannelSignal<T>
+where
+    T: Clone + Send + 'static,
+    Fu: Future<Output = ()> + Send + 'static,
+{
+    let recent = initial
+        .iter()
+        .take(options.buffer)
+        .cloned()
+        .collect::<VecDeque<_>>();
+    let (latest, set_latest) = create_signal(initial);
+    let (recent, set_recent) = create_signal(recent);
+    let (closed, set_closed) = create_signal(false);
+    let (lagged, set_lagged) = create_signal(0);
+
+    let applied = Arc::new(Notify::new());
+    let sender = create_sync_handler({
+        let applied = Arc::clone(&applied);
+        move |received: Received<T>| {
+            batch(|| {
+                if options.buffer > 0 && !received.items.is_empty() {
+                    set_recent.update(|recent| {
+                        recent.extend(received.items.iter().cloned());
+                        let excess = recent.len().saturating_sub(options.buffer);
+                        recent.drain(..excess);
+                    });
+                }
+                if let Some(item) = received.items.into_iter().last() {
+                    set_latest.set(Some(item));
+                }
+                if received.lagged > 0 {
+                    set_lagged.update(|lagged| *lagged += received.lagged);
+                }
+                if received.closed {
+                    set_closed.set(true);
+                }
+            });
+            applied.notify_one();
+        }
+    });
+
+    let handle = tokio::spawn(task(Forwarder { sender, applied }));
+    on_cleanup(move || handle.abort());
+
+    ChannelSignal {
+        latest,
+        recent,
+        closed,
+        lagged,
+    }
+}
+
+impl<T> ChannelSignal<T> {
+    /// The most recent items, oldest first, up to
+    /// [`ChannelOptions::buffer`] of them.
+    pub fn recent(&self) -> Signal<VecDeque<T>>
+    where
+        T: Clone,
+    {
+        self.recent.into()
+    }
+
+    /// Whether every sender has been dropped, so nothing more will be
+    /// received.
+    pub fn closed(&self) -> Signal<bool> {
+        self.closed.into()
+    }
+
+    /// How many items were skipped so far, because the channel overwrote
+    /// them before they could be received. This is only ever nonzero for
+    /// `broadcast` channels.
+    pub fn lagged(&self) -> Signal<u64> {
+        self.lagged.into()
+    }
+}
+
+/// Sends every value of `signal` to `sender`, so that code outside the
+/// runtime, such as other threads or Tokio tasks, can follow it, until the
+/// current owner is cleaned up and `sender` is dropped.
+///
+/// This is the counterpart of [`create_signal_from_watch`]. Unlike
+/// [`SignalStream::to_stream`](crate::SignalStream::to_stream), a slow
+/// receiver only ever misses values rather than making them pile up.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let (count, set_count) = create_signal(1);
+/// let double = create_memo(move |_| count.get() * 2);
+///
+/// let (tx, rx) = tokio::sync::watch::channel(0);
+/// mirror_to_watch(double, tx);
+/// assert_eq!(*rx.borrow(), 2);
+///
+/// set_count.set(5);
+/// assert_eq!(*rx.borrow(), 10);
+/// # runtime.dispose();
+/// ```
+pub fn mirror_to_watch<T>(signal: impl SignalWith<Value = T> + 'static, sender: watch::Sender<T>)
+where
+    T: Clone + 'static,
+{
+    create_isomorphic_effect(move |_| {
+        signal.with(|value| sender.send_replace(value.clone()));
+    });
+}
+
+impl<T> Clone for ChannelSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ChannelSignal<T> {}
+
+impl<T> SignalWith for ChannelSignal<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        self.latest.with(f)
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        SignalWith::try_with(&self.latest, f)
+    }
+}
+
+impl<T> SignalWithUntracked for ChannelSignal<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        self.latest.with_untracked(f)
+    }
+
+    fn try_with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.latest.try_with_untracked(f)
+    }
+}
+
+impl<T: Clone> SignalGet for ChannelSignal<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get(&self) -> Option<T> {
+        self.latest.get()
+    }
+
+    fn try_get(&self) -> Option<Option<T>> {
+        self.latest.try_get()
+    }
+}
+
+impl<T: Clone> SignalGetUntracked for ChannelSignal<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get_untracked(&self) -> Option<T> {
+        self.latest.get_untracked()
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<T>> {
+        self.latest.try_get_untracked()
+    }
+}
@@ -0,0 +1,888 @@
+use crate::{
+    create_trigger, on_cleanup, store_value, trigger::KeyedTriggers, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, StoredValue, Trigger,
+};
+use indexmap::IndexMap;
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    hash::Hash,
+    rc::Rc,
+};
+
+/// A change made to a [`ReactiveVec`], as passed to the callbacks registered
+/// with [`ReactiveVec::on_diff`].
+///
+/// Indices refer to the `Vec` right after the change was made, so applying
+/// the diffs in order to a copy of the `Vec` keeps it in sync.
+#[derive(Debug, PartialEq)]
+pub enum VecDiff<'a, T> {
+    /// `value` was inserted at `index`, shifting everything after it.
+    Insert {
+        /// Where the value was inserted.
+        index: usize,
+        /// The value that was inserted.
+        value: &'a T,
+    },
+    /// The item at `index` was removed, shifting everything after it.
+    Remove {
+        /// Where the value was removed from.
+        index: usize,
+    },
+    /// The item at `index` was replaced or modified in place.
+    Update {
+        /// The index of the item that changed.
+        index: usize,
+        /// Its new value.
+        value: &'a T,
+    },
+    /// The item at `from` was removed, and inserted again at `to`.
+    Move {
+        /// The index the item used to be at.
+        from: usize,
+        /// The index the item is at now.
+        to: usize,
+    },
+    /// Every item was removed.
+    Clear,
+    /// The whole `Vec` was changed at once, through [`SignalSet`] or
+    /// [`SignalUpdate`].
+    Replace {
+        /// The new items.
+        items: &'a [T],
+    },
+}
+
+/// A change made to a [`ReactiveMap`], as passed to the callbacks registered
+/// with [`ReactiveMap::on_diff`].
+#[derive(Debug)]
+pub enum MapDiff<'a, K, V> {
+    /// A new entry was inserted at the end of the map.
+    Insert {
+        /// The key that was inserted.
+        key: &'a K,
+        /// Its value.
+        value: &'a V,
+    },
+    /// The value of an existing entry was replaced or modified in place.
+    Update {
+        /// The key of the entry that changed.
+        key: &'a K,
+        /// Its new value.
+        value: &'a V,
+    },
+    /// An entry was removed.
+    Remove {
+        /// The key that was removed.
+        key: &'a K,
+    },
+    /// Every entry was removed.
+    Clear,
+    /// The whole map was changed at once, through [`SignalSet`] or
+    /// [`SignalUpdate`].
+    Replace {
+        /// The new entries.
+        entries: &'a IndexMap<K, V>,
+    },
+}
+
+// not derived, as comparing `IndexMap`s also needs `K: Hash + Eq`
+impl<K, V> PartialEq for MapDiff<'_, K, V>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Insert { key, value },
+                Self::Insert {
+                    key: other_key,
+                    value: other_value,
+                },
+            )
+            | (
+                Self::Update { key, value },
+                Self::Update {
+                    key: other_key,
+                    value: other_value,
+                },
+            ) => key == other_key && value == other_value,
+            (Self::Remove { key }, Self::Remove { key: other_key }) => key == other_key,
+            (Self::Clear, Self::Clear) => true,
+            (Self::Replace { entries }, Self::Replace { entries: other }) => entries == other,
+            _ => false,
+        }
+    }
+}
+
+/// A `Vec` that publishes what changed in it instead of replacing its whole
+/// value, created with [`create_reactive_vec`].
+///
+/// Readers can subscribe to just the parts they need:
+/// - [`.len()`](ReactiveVec::len) only changes when items are inserted or removed,
+/// - [`.get()`](ReactiveVec::get) and [`.with_item()`](ReactiveVec::with_item)
+///   only change when the item at that index does (which includes items
+///   shifting around),
+/// - [`.with()`](SignalWith::with) changes whenever anything does.
+///
+/// Anything that keeps its own copy of the items, such as a list view, can
+/// use [`.on_diff()`](ReactiveVec::on_diff) to apply each change directly,
+/// without comparing the old and new `Vec`.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # let runtime = create_runtime();
+/// let todos = create_reactive_vec(vec!["write code"]);
+///
+/// let len_runs = Rc::new(Cell::new(0));
+/// create_isomorphic_effect({
+///     let len_runs = Rc::clone(&len_runs);
+///     move |_| {
+///         todos.len();
+///         len_runs.set(len_runs.get() + 1);
+///     }
+/// });
+///
+/// let mirror = Rc::new(std::cell::RefCell::new(todos.get_untracked()));
+/// todos.on_diff({
+///     let mirror = Rc::clone(&mirror);
+///     move |diff| match diff {
+///         VecDiff::Insert { index, value } => mirror.borrow_mut().insert(*index, *value),
+///         VecDiff::Update { index, value } => mirror.borrow_mut()[*index] = *value,
+///         VecDiff::Remove { index } => _ = mirror.borrow_mut().remove(*index),
+///         VecDiff::Move { from, to } => {
+///             let item = mirror.borrow_mut().remove(*from);
+///             mirror.borrow_mut().insert(*to, item);
+///         }
+///         VecDiff::Clear => mirror.borrow_mut().clear(),
+///         VecDiff::Replace { items } => *mirror.borrow_mut() = items.to_vec(),
+///     }
+/// });
+///
+/// todos.push("test code");
+/// assert_eq!(len_runs.get(), 2);
+///
+/// // updating an item doesn't change the length
+/// todos.set_item(0, "write more code");
+/// assert_eq!(len_runs.get(), 2);
+///
+/// assert_eq!(*mirror.borrow(), ["write more code", "test code"]);
+/// # runtime.dispose();
+/// ```
+pub struct ReactiveVec<T>
+where
+    T: 'static,
+{
+    state: StoredValue<VecState<T>>,
+}
+
+/// A map that publishes what changed in it instead of replacing its whole
+/// value, created with [`create_reactive_map`].
+///
+/// Entries are kept in insertion order. As with [`ReactiveVec`], readers can
+/// subscribe to the length with [`.len()`](ReactiveMap::len), to a single
+/// entry with [`.get()`](ReactiveMap::get),
+/// [`.with_entry()`](ReactiveMap::with_entry) or
+/// [`.contains_key()`](ReactiveMap::contains_key), or to every change with
+/// [`.with()`](SignalWith::with), and changes can be applied directly with
+/// [`.on_diff()`](ReactiveMap::on_diff).
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let scores = create_reactive_map([("alice", 1)]);
+/// let bob = create_memo(move |_| scores.get(&"bob"));
+/// assert_eq!(bob.get(), None);
+///
+/// scores.insert("bob", 2);
+/// scores.update_entry(&"bob", |score| *score += 1);
+/// assert_eq!(bob.get(), Some(3));
+/// assert_eq!(scores.len(), 2);
+/// # runtime.dispose();
+/// ```
+pub struct ReactiveMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    state: StoredValue<MapState<K, V>>,
+}
+
+/// Creates a [`ReactiveVec`] holding the given items.
+#[track_caller]
+pub fn create_reactive_vec<T>(items: Vec<T>) -> ReactiveVec<T> {
+    ReactiveVec::new(items)
+}
+
+/// Creates a [`ReactiveMap`] holding the given entries.
+#[track_caller]
+pub fn create_reactive_map<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> ReactiveMap<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    ReactiveMap::new(entries)
+}
+
+type VecListener<T> = Rc<dyn Fn(&VecDiff<'_, T>)>;
+type MapListener<K, V> = Rc<dyn Fn(&MapDiff<'_, K, V>)>;
+
+struct VecState<T: 'static> {
+    items: RefCell<Vec<T>>,
+    all: Trigger,
+    len: Trigger,
+    entries: KeyedTriggers<usize>,
+    listeners: RefCell<Vec<(usize, VecListener<T>)>>,
+    next_listener: Cell<usize>,
+}
+
+struct MapState<K: 'static, V: 'static> {
+    entries: RefCell<IndexMap<K, V>>,
+    all: Trigger,
+    len: Trigger,
+    keys: KeyedTriggers<K>,
+    listeners: RefCell<Vec<(usize, MapListener<K, V>)>>,
+    next_listener: Cell<usize>,
+}
+
+/// What changed in a [`ReactiveVec`], before it is turned into a [`VecDiff`].
+#[derive(Clone, Copy)]
+enum VecChange {
+    Insert(usize),
+    Remove(usize),
+    Update(usize),
+    Move(usize, usize),
+    Clear,
+    Replace,
+}
+
+/// What changed in a [`ReactiveMap`], before it is turned into a [`MapDiff`].
+enum MapChange<K> {
+    Insert(K),
+    Update(K),
+    Remove(K),
+    Clear,
+    Replace,
+}
+
+impl<T> VecState<T> {
+    /// Tells the listeners, and then the subscribers of everything that was
+    /// affected, about a change that has just been made.
+    fn changed(&self, change: VecChange) {
+        let listeners = self
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| Rc::clone(listener))
+            .collect::<Vec<_>>();
+        if !listeners.is_empty() {
+            let items = self.items.borrow();
+            let diff = match change {
+                VecChange::Insert(index) => VecDiff::Insert {
+                    index,
+                    value: &items[index],
+                },
+                VecChange::Remove(index) => VecDiff::Remove { index },
+                VecChange::Update(index) => VecDiff::Update {
+                    index,
+                    value: &items[index],
+                },
+                VecChange::Move(from, to) => VecDiff::Move { from, to },
+                VecChange::Clear => VecDiff::Clear,
+                VecChange::Replace => VecDiff::Replace { items: &items },
+            };
+            for listener in listeners {
+                listener(&diff);
+            }
+        }
+
+        // inserting or removing an item shifts every item after it
+        let (len_changed, affected) = match change {
+            VecChange::Insert(start) | VecChange::Remove(start) => (true, start..=usize::MAX),
+            VecChange::Update(index) => (false, index..=index),
+            VecChange::Move(from, to) => (false, from.min(to)..=from.max(to)),
+            VecChange::Clear | VecChange::Replace => (true, 0..=usize::MAX),
+        };
+        crate::batch(|| {
+            self.all.notify();
+            if len_changed {
+                self.len.notify();
+            }
+            self.entries.notify_where(|index| affected.contains(index));
+        });
+
+        if len_changed {
+            // drop the triggers of indices past the end once the effects
+            // that read them have run again
+            let len = self.items.borrow().len();
+            self.entries.release_where(|index| *index >= len);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> MapState<K, V> {
+    /// Tells the listeners, and then the subscribers of everything that was
+    /// affected, about a change that has just been made.
+    fn changed(&self, change: MapChange<K>) {
+        let listeners = self
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| Rc::clone(listener))
+            .collect::<Vec<_>>();
+        if !listeners.is_empty() {
+            let entries = self.entries.borrow();
+            let diff = match &change {
+                MapChange::Insert(key) => MapDiff::Insert {
+                    key,
+                    value: &entries[key],
+                },
+                MapChange::Update(key) => MapDiff::Update {
+                    key,
+                    value: &entries[key],
+                },
+                MapChange::Remove(key) => MapDiff::Remove { key },
+                MapChange::Clear => MapDiff::Clear,
+                MapChange::Replace => MapDiff::Replace { entries: &entries },
+            };
+            for listener in listeners {
+                listener(&diff);
+            }
+        }
+
+        crate::batch(|| {
+            self.all.notify();
+            match &change {
+                MapChange::Insert(key) | MapChange::Remove(key) => {
+                    self.len.notify();
+                    self.keys.notify_where(|other| other == key);
+                }
+                MapChange::Update(key) => self.keys.notify_where(|other| other == key),
+                MapChange::Clear | MapChange::Replace => {
+                    self.len.notify();
+                    self.keys.notify_where(|_| true);
+                }
+            }
+        });
+
+        // drop the triggers of missing keys once the effects that read them
+        // have run again, so a map with changing keys doesn't keep one for
+        // every key it ever held
+        if matches!(
+            change,
+            MapChange::Remove(_) | MapChange::Clear | MapChange::Replace
+        ) {
+            let entries = self.entries.borrow();
+            self.keys.release_where(|key| !entries.contains_key(key));
+        }
+    }
+}
+
+macro_rules! impl_listeners {
+    ($state:ident < $($param:ident),* >, $diff:ident) => {
+        impl<$($param),*> $state<$($param),*> {
+            fn add_listener(&self, listener: Rc<dyn Fn(&$diff<'_, $($param),*>)>) -> usize {
+                let id = self.next_listener.get();
+                self.next_listener.set(id + 1);
+                self.listeners.borrow_mut().push((id, listener));
+                id
+            }
+
+            fn remove_listener(&self, id: usize) {
+                self.listeners.borrow_mut().retain(|(other, _)| *other != id);
+            }
+        }
+    };
+}
+
+impl_listeners!(VecState<T>, VecDiff);
+impl_listeners!(MapState<K, V>, MapDiff);
+
+impl<T> ReactiveVec<T> {
+    /// Creates a [`ReactiveVec`] holding the given items.
+    #[track_caller]
+    pub fn new(items: Vec<T>) -> Self {
+        let state = store_value(VecState {
+            items: RefCell::new(items),
+            all: create_trigger(),
+            len: create_trigger(),
+            entries: KeyedTriggers::new(),
+            listeners: Default::default(),
+            next_listener: Default::default(),
+        });
+        Self { state }
+    }
+
+    /// Returns the number of items, subscribing only to changes in length.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.state.with_value(|state| {
+            state.len.track();
+            state.items.borrow().len()
+        })
+    }
+
+    /// Returns `true` if there are no items, subscribing only to changes in
+    /// length.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Applies `f` to the item at `index`, if there is one, subscribing only
+    /// to changes of that item.
+    #[track_caller]
+    pub fn with_item<O>(&self, index: usize, f: impl FnOnce(&T) -> O) -> Option<O> {
+        self.state.with_value(|state| {
+            state.entries.track(&index);
+            state.items.borrow().get(index).map(f)
+        })
+    }
+
+    /// Clones the item at `index`, if there is one, subscribing only to
+    /// changes of that item.
+    #[track_caller]
+    pub fn get(&self, index: usize) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with_item(index, T::clone)
+    }
+
+    /// Appends an item.
+    #[track_caller]
+    pub fn push(&self, value: T) {
+        self.state.with_value(|state| {
+            let index = {
+                let mut items = state.items.borrow_mut();
+                items.push(value);
+                items.len() - 1
+            };
+            state.changed(VecChange::Insert(index));
+        })
+    }
+
+    /// Removes the last item and returns it, if there is one.
+    #[track_caller]
+    pub fn pop(&self) -> Option<T> {
+        self.state.with_value(|state| {
+            let (value, index) = {
+                let mut items = state.items.borrow_mut();
+                let value = items.pop()?;
+                (value, items.len())
+            };
+            state.changed(VecChange::Remove(index));
+            Some(value)
+        })
+    }
+
+    /// Inserts an item at `index`, shifting everything after it.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        self.state.with_value(|state| {
+            state.items.borrow_mut().insert(index, value);
+            state.changed(VecChange::Insert(index));
+        })
+    }
+
+    /// Removes the item at `index` and returns it, shifting everything after
+    /// it.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        self.state.with_value(|state| {
+            let value = state.items.borrow_mut().remove(index);
+            state.changed(VecChange::Remove(index));
+            value
+        })
+    }
+
+    /// Replaces the item at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn set_item(&self, index: usize, value: T) {
+        self.update_item(index, |item| *item = value);
+    }
+
+    /// Modifies the item at `index` in place.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn update_item<O>(&self, index: usize, f: impl FnOnce(&mut T) -> O) -> O {
+        self.state.with_value(|state| {
+            let result = f(&mut state.items.borrow_mut()[index]);
+            state.changed(VecChange::Update(index));
+            result
+        })
+    }
+
+    /// Moves the item at `from` so that it ends up at `to`, shifting the
+    /// items in between.
+    ///
+    /// # Panics
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.state.with_value(|state| {
+            {
+                let mut items = state.items.borrow_mut();
+                assert!(to < items.len(), "move destination {to} is out of bounds");
+                let item = items.remove(from);
+                items.insert(to, item);
+            }
+            state.changed(VecChange::Move(from, to));
+        })
+    }
+
+    /// Removes every item.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.state.with_value(|state| {
+            state.items.borrow_mut().clear();
+            state.changed(VecChange::Clear);
+        })
+    }
+
+    /// Calls `f` with every change made to the items from now on, until the
+    /// current owner is disposed.
+    ///
+    /// `f` is called right after each change, so it sees every change even
+    /// within a [`batch`](crate::batch). It must not modify the `ReactiveVec`
+    /// itself.
+    #[track_caller]
+    pub fn on_diff(&self, f: impl Fn(&VecDiff<'_, T>) + 'static) {
+        let id = self
+            .state
+            .with_value(|state| state.add_listener(Rc::new(f)));
+        let state = self.state;
+        on_cleanup(move || {
+            _ = state.try_with_value(|state| state.remove_listener(id));
+        });
+    }
+
+    fn try_with_all<O>(&self, track: bool, f: impl FnOnce(&Vec<T>) -> O) -> Option<O> {
+        self.state.try_with_value(|state| {
+            if track {
+                state.all.track();
+            }
+            f(&state.items.borrow())
+        })
+    }
+
+    fn try_update_all<O>(&self, f: impl FnOnce(&mut Vec<T>) -> O) -> Option<O> {
+        self.state.try_with_value(|state| {
+            let result = f(&mut state.items.borrow_mut());
+            state.changed(VecChange::Replace);
+            result
+        })
+    }
+}
+
+impl<K, V> ReactiveMap<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Creates a [`ReactiveMap`] holding the given entries.
+    #[track_caller]
+    pub fn new(entries: impl IntoIterator<Item = (K, V)>) -> Self {
+        let state = store_value(MapState {
+            entries: RefCell::new(entries.into_iter().collect()),
+            all: create_trigger(),
+            len: create_trigger(),
+            keys: KeyedTriggers::new(),
+            listeners: Default::default(),
+            next_listener: Default::default(),
+        });
+        Self { state }
+    }
+
+    /// Returns the number of entries, subscribing only to changes in length.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.state.with_value(|state| {
+            state.len.track();
+            state.entries.borrow().len()
+        })
+    }
+
+    /// Returns `true` if there are no entries, subscribing only to changes in
+    /// length.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if there is an entry for `key`, subscribing only to
+    /// changes of that entry.
+    #[track_caller]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.with_entry(key, |_| ()).is_some()
+    }
+
+    /// Applies `f` to the value for `key`, if there is one, subscribing only
+    /// to changes of that entry.
+    #[track_caller]
+    pub fn with_entry<O>(&self, key: &K, f: impl FnOnce(&V) -> O) -> Option<O> {
+        self.state.with_value(|state| {
+            state.keys.track(key);
+            state.entries.borrow().get(key).map(f)
+        })
+    }
+
+    /// Clones the value for `key`, if there is one, subscribing only to
+    /// changes of that entry.
+    #[track_caller]
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.with_entry(key, V::clone)
+    }
+
+    /// Inserts a value for `key`, returning the previous value if there was
+    /// one. New keys are added at the end; existing keys keep their place.
+    #[track_caller]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.state.with_value(|state| {
+            let previous = state.entries.borrow_mut().insert(key.clone(), value);
+            state.changed(if previous.is_some() {
+                MapChange::Update(key)
+            } else {
+                MapChange::Insert(key)
+            });
+            previous
+        })
+    }
+
+    /// Removes the entry for `key`, returning its value if there was one. The
+    /// entries after it keep their order.
+    #[track_caller]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.state.with_value(|state| {
+            let (key, value) = state.entries.borrow_mut().shift_remove_entry(key)?;
+            state.changed(MapChange::Remove(key));
+            Some(value)
+        })
+    }
+
+    /// Modifies the value for `key` in place, if there is one.
+    #[track_caller]
+    pub fn update_entry<O>(&self, key: &K, f: impl FnOnce(&mut V) -> O) -> Option<O> {
+        self.state.with_value(|state| {
+            let result = state.entries.borrow_mut().get_mut(key).map(f)?;
+            state.changed(MapChange::Update(key.clone()));
+            Some(result)
+        })
+    }
+
+    /// Removes every entry.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.state.with_value(|state| {
+            state.entries.borrow_mut().clear();
+            state.changed(MapChange::Clear);
+        })
+    }
+
+    /// Calls `f` with every change made to the entries from now on, until
+    /// the current owner is disposed.
+    ///
+    /// `f` is called right after each change, so it sees every change even
+    /// within a [`batch`](crate::batch). It must not modify the `ReactiveMap`
+    /// itself.
+    #[track_caller]
+    pub fn on_diff(&self, f: impl Fn(&MapDiff<'_, K, V>) + 'static) {
+        let id = self
+            .state
+            .with_value(|state| state.add_listener(Rc::new(f)));
+        let state = self.state;
+        on_cleanup(move || {
+            _ = state.try_with_value(|state| state.remove_listener(id));
+        });
+    }
+
+    fn try_with_all<O>(&self, track: bool, f: impl FnOnce(&IndexMap<K, V>) -> O) -> Option<O> {
+        self.state.try_with_value(|state| {
+            if track {
+                state.all.track();
+            }
+            f(&state.entries.borrow())
+        })
+    }
+
+    fn try_update_all<O>(&self, f: impl FnOnce(&mut IndexMap<K, V>) -> O) -> Option<O> {
+        self.state.try_with_value(|state| {
+            let result = f(&mut state.entries.borrow_mut());
+            state.changed(MapChange::Replace);
+            result
+        })
+    }
+}
+
+impl<T> Clone for ReactiveVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ReactiveVec<T> {}
+
+impl<T> PartialEq for ReactiveVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<T> Eq for ReactiveVec<T> {}
+
+impl<T> fmt::Debug for ReactiveVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReactiveVec")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<K, V> Clone for ReactiveMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for ReactiveMap<K, V> {}
+
+impl<K, V> PartialEq for ReactiveMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<K, V> Eq for ReactiveMap<K, V> {}
+
+impl<K, V> fmt::Debug for ReactiveMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReactiveMap")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn panic_disposed(kind: &str) -> ! {
+    panic!("Tried to access a {kind} that has been disposed.")
+}
+
+macro_rules! impl_signal_traits {
+    ($ty:ident < $($param:ident),* >, $value:ty, $name:literal, $($bounds:tt)*) => {
+        impl<$($param),*> SignalWith for $ty<$($param),*> where $($bounds)* {
+            type Value = $value;
+
+            #[track_caller]
+            fn with<O>(&self, f: impl FnOnce(&$value) -> O) -> O {
+                match self.try_with_all(true, f) {
+                    Some(value) => value,
+                    None => panic_disposed($name),
+                }
+            }
+
+            fn try_with<O>(&self, f: impl FnOnce(&$value) -> O) -> Option<O> {
+                self.try_with_all(true, f)
+            }
+        }
+
+        impl<$($param),*> SignalWithUntracked for $ty<$($param),*> where $($bounds)* {
+            type Value = $value;
+
+            #[track_caller]
+            fn with_untracked<O>(&self, f: impl FnOnce(&$value) -> O) -> O {
+                match self.try_with_all(false, f) {
+                    Some(value) => value,
+                    None => panic_disposed($name),
+                }
+            }
+
+            #[track_caller]
+            fn try_with_untracked<O>(&self, f: impl FnOnce(&$value) -> O) -> Option<O> {
+                self.try_with_all(false, f)
+            }
+        }
+
+        impl<$($param),*> SignalGet for $ty<$($param),*>
+        where
+            $value: Clone,
+            $($bounds)*
+        {
+            type Value = $value;
+
+            #[track_caller]
+            fn get(&self) -> $value {
+                self.with(Clone::clone)
+            }
+
+            fn try_get(&self) -> Option<$value> {
+                self.try_with(Clone::clone)
+            }
+        }
+
+        impl<$($param),*> SignalGetUntracked for $ty<$($param),*>
+        where
+            $value: Clone,
+            $($bounds)*
+        {
+            type Value = $value;
+
+            #[track_caller]
+            fn get_untracked(&self) -> $value {
+                self.with_untracked(Clone::clone)
+            }
+
+            fn try_get_untracked(&self) -> Option<$value> {
+                self.try_with_untracked(Clone::clone)
+            }
+        }
+
+        impl<$($param),*> SignalUpdate for $ty<$($param),*> where $($bounds)* {
+            type Value = $value;
+
+            #[track_caller]
+            fn update(&self, f: impl FnOnce(&mut $value)) {
+                if self.try_update_all(f).is_none() {
+                    panic_disposed($name)
+                }
+            }
+
+            fn try_update<O>(&self, f: impl FnOnce(&mut $value) -> O) -> Option<O> {
+                self.try_update_all(f)
+            }
+        }
+
+        impl<$($param),*> SignalSet for $ty<$($param),*> where $($bounds)* {
+            type Value = $value;
+
+            #[track_caller]
+            fn set(&self, new_value: $value) {
+                self.update(|value| *value = new_value);
+            }
+
+            fn try_set(&self, new_value: $value) -> Option<$value> {
+                let mut new_value = Some(new_value);
+                self.try_update_all(|value| *value = new_value.take().unwrap());
+                new_value
+            }
+        }
+    };
+}
+
+impl_signal_traits!(ReactiveVec<T>, Vec<T>, "ReactiveVec",);
+impl_signal_traits!(ReactiveMap<K, V>, IndexMap<K, V>, "ReactiveMap", K: Hash + Eq + Clone);
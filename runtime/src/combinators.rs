@@ -0,0 +1,174 @@
+use crate::{create_isomorphic_effect, create_memo, Memo, SignalWith};
+
+/// Adapters that derive new [`Memo`]s from any readable signal, such as a
+/// [`ReadSignal`](crate::ReadSignal), [`RwSignal`](crate::RwSignal),
+/// [`Memo`] or [`Signal`](crate::Signal).
+///
+/// Every adapter returns a memo, so its dependents only run once per change
+/// no matter how many adapters a value passes through, and only if what the
+/// adapter produced actually changed.
+///
+/// The adapters that keep state between changes ([`filter`](SignalExt::filter),
+/// [`fold`](SignalExt::fold), [`previous`](SignalExt::previous) and
+/// [`changed_count`](SignalExt::changed_count)) see every change, even if
+/// nothing reads them in between, except that changes made in the same
+/// [`batch`](crate::batch) count as one.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let (count, set_count) = create_signal(1);
+/// let even_doubled = count.filter(|n| n % 2 == 0).map(|n| n.map(|n| n * 2));
+/// assert_eq!(even_doubled.get(), None);
+///
+/// set_count.set(2);
+/// set_count.set(3);
+/// assert_eq!(even_doubled.get(), Some(4));
+/// # runtime.dispose();
+/// ```
+pub trait SignalExt: SignalWith + Clone + 'static
+where
+    Self::Value: 'static,
+{
+    /// Derives a memo holding `f` applied to the value of this signal.
+    #[track_caller]
+    fn map<U>(self, f: impl Fn(&Self::Value) -> U + 'static) -> Memo<U>
+    where
+        U: PartialEq + 'static,
+    {
+        create_memo(move |_| self.with(&f))
+    }
+
+    /// Derives a memo holding the values of this signal and `other`, which
+    /// changes whenever either of them does.
+    #[track_caller]
+    fn zip<S>(self, other: S) -> Memo<(Self::Value, S::Value)>
+    where
+        S: SignalWith + 'static,
+        Self::Value: Clone + PartialEq,
+        S::Value: Clone + PartialEq,
+    {
+        create_memo(move |_| (self.with(Clone::clone), other.with(Clone::clone)))
+    }
+
+    /// Derives a memo holding the last value of this signal for which
+    /// `predicate` returned `true`, or `None` if there hasn't been one yet.
+    /// Values that are rejected leave it unchanged.
+    #[track_caller]
+    fn filter(self, predicate: impl Fn(&Self::Value) -> bool + 'static) -> Memo<Option<Self::Value>>
+    where
+        Self::Value: Clone + PartialEq,
+    {
+        eager(create_memo(
+            move |accepted: Option<&Option<Self::Value>>| {
+                self.with(|value| {
+                    if predicate(value) {
+                        Some(value.clone())
+                    } else {
+                        accepted.cloned().flatten()
+                    }
+                })
+            },
+        ))
+    }
+
+    /// Derives a memo that starts as `f(&init, value)`, and then combines
+    /// what it holds with every new value of this signal.
+    ///
+    /// ```
+    /// # use goober_runtime::*;
+    /// # let runtime = create_runtime();
+    /// let (count, set_count) = create_signal(1);
+    /// let history = count.fold(Vec::new(), |seen, n| [seen.as_slice(), &[*n]].concat());
+    ///
+    /// set_count.set(2);
+    /// set_count.set(3);
+    /// assert_eq!(history.get(), vec![1, 2, 3]);
+    /// # runtime.dispose();
+    /// ```
+    #[doc(alias = "scan")]
+    #[track_caller]
+    fn fold<A>(self, init: A, f: impl Fn(&A, &Self::Value) -> A + 'static) -> Memo<A>
+    where
+        A: PartialEq + 'static,
+    {
+        eager(create_memo(move |acc: Option<&A>| {
+            self.with(|value| f(acc.unwrap_or(&init), value))
+        }))
+    }
+
+    /// Derives a memo holding the value of this signal, which only changes
+    /// when `key` returns something different for the new value.
+    #[track_caller]
+    fn distinct_by<K>(self, key: impl Fn(&Self::Value) -> K + 'static) -> Memo<Self::Value>
+    where
+        K: PartialEq,
+        Self::Value: Clone + PartialEq,
+    {
+        create_memo(move |last: Option<&Self::Value>| {
+            self.with(|value| match last {
+                Some(last) if key(last) == key(value) => last.clone(),
+                _ => value.clone(),
+            })
+        })
+    }
+
+    /// Derives a memo holding the value this signal had before it last
+    /// changed, or `None` if it hasn't changed yet.
+    ///
+    /// ```
+    /// # use goober_runtime::*;
+    /// # let runtime = create_runtime();
+    /// let (page, set_page) = create_signal("home");
+    /// let previous = page.previous();
+    /// assert_eq!(previous.get(), None);
+    ///
+    /// set_page.set("settings");
+    /// assert_eq!(previous.get(), Some("home"));
+    /// # runtime.dispose();
+    /// ```
+    #[track_caller]
+    fn previous(self) -> Memo<Option<Self::Value>>
+    where
+        Self::Value: Clone + PartialEq,
+    {
+        // the current value is kept alongside the previous one, so that
+        // setting the signal to the same value doesn't lose it
+        let values = eager(create_memo(
+            move |last: Option<&(Self::Value, Option<Self::Value>)>| {
+                self.with(|value| match last {
+                    Some((current, previous)) if current == value => {
+                        (current.clone(), previous.clone())
+                    }
+                    Some((current, _)) => (value.clone(), Some(current.clone())),
+                    None => (value.clone(), None),
+                })
+            },
+        ));
+        create_memo(move |_| values.with(|(_, previous)| previous.clone()))
+    }
+
+    /// Derives a memo counting how many times this signal has notified its
+    /// subscribers since the memo was created.
+    #[track_caller]
+    fn changed_count(self) -> Memo<usize> {
+        eager(create_memo(move |count: Option<&usize>| {
+            self.track();
+            count.map_or(0, |count| count + 1)
+        }))
+    }
+}
+
+impl<S> SignalExt for S
+where
+    S: SignalWith + Clone + 'static,
+    S::Value: 'static,
+{
+}
+
+/// Keeps `memo` up to date whenever what it depends on changes, rather than
+/// only when it is read, so that it doesn't miss any values.
+fn eager<T>(memo: Memo<T>) -> Memo<T> {
+    create_isomorphic_effect(move |_| memo.track());
+    memo
+}
@@ -1,6 +1,49 @@
-use crate::{node::NodeId, with_runtime, Disposer, Runtime, SignalDispose};
+use crate::{node::NodeId, with_runtime, Disposer, Runtime, RuntimeId, SignalDispose};
 use std::{any::Any, cell::RefCell, marker::PhantomData, rc::Rc};
 
+/// When an effect runs, relative to other effects that are waiting to run
+/// at the same time.
+///
+/// Pending effects run one phase at a time, in the order below. Whenever a
+/// phase has run, the earliest phase with effects left runs next, so that
+/// an effect always sees what every effect of an earlier phase did in
+/// response to the same change.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EffectPhase {
+    /// Effects that update state before anything is measured.
+    PreLayout,
+    /// Effects that measure and lay things out.
+    Layout,
+    /// Effects that draw, such as those created with
+    /// [`create_render_effect`].
+    Render,
+    /// Effects that react to what was drawn. Effects created with
+    /// [`create_effect`] or [`create_isomorphic_effect`] run in this phase.
+    #[default]
+    PostRender,
+}
+
+impl EffectPhase {
+    /// Every phase, in the order they run.
+    pub const ALL: [EffectPhase; 4] = [
+        EffectPhase::PreLayout,
+        EffectPhase::Layout,
+        EffectPhase::Render,
+        EffectPhase::PostRender,
+    ];
+}
+
+/// When an effect created with [`create_effect_with_options`] runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EffectOptions {
+    /// The phase the effect runs in.
+    pub phase: EffectPhase,
+    /// Effects with a higher priority run before others of the same phase.
+    /// Effects with the same priority run in the order they were marked
+    /// dirty. Defaults to `0`.
+    pub priority: i32,
+}
+
 /// Effects run a certain chunk of code whenever the signals they depend on change.
 /// `create_effect` queues the given function to run once, tracks its dependence
 /// on any signal values read within it, and reruns the function whenever the value
@@ -264,14 +307,61 @@ where
         )
     )
 )]
+#[track_caller]
 #[inline(always)]
 pub fn create_render_effect<T>(f: impl Fn(Option<T>) -> T + 'static) -> Effect<T>
+where
+    T: 'static,
+{
+    create_effect_with_options(
+        f,
+        EffectOptions {
+            phase: EffectPhase::Render,
+            priority: 0,
+        },
+    )
+}
+
+/// Creates an effect that runs in the given [`EffectPhase`], and with the
+/// given priority within it, whenever it has to run again at the same time as
+/// other effects. Like [`create_isomorphic_effect`], it runs immediately, and
+/// on the server as well as the client.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # let runtime = create_runtime();
+/// let order = Rc::new(RefCell::new(Vec::new()));
+/// let (size, set_size) = create_signal(10);
+///
+/// for (name, phase) in [("paint", EffectPhase::Render), ("measure", EffectPhase::Layout)] {
+///     let order = Rc::clone(&order);
+///     create_effect_with_options(
+///         move |_| order.borrow_mut().push((name, size.get())),
+///         EffectOptions { phase, ..Default::default() },
+///     );
+/// }
+/// order.borrow_mut().clear();
+///
+/// set_size.set(20);
+/// assert_eq!(*order.borrow(), [("measure", 20), ("paint", 20)]);
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+#[inline(always)]
+pub fn create_effect_with_options<T>(
+    f: impl Fn(Option<T>) -> T + 'static,
+    options: EffectOptions,
+) -> Effect<T>
 where
     T: 'static,
 {
     let runtime = Runtime::current();
     let id = runtime.create_effect(f);
     _ = with_runtime(|runtime| {
+        if options != EffectOptions::default() {
+            runtime.effect_options.borrow_mut().insert(id, options);
+        }
         runtime.update_if_necessary(id);
     });
     Effect {
@@ -312,6 +402,12 @@ where
 
 pub(crate) trait AnyComputation {
     fn run(&self, value: Rc<RefCell<dyn Any>>) -> bool;
+
+    /// Drops the value once nothing subscribes to the computation any more,
+    /// if it should be, and returns whether it was.
+    fn release(&self, _value: Rc<RefCell<dyn Any>>) -> bool {
+        false
+    }
 }
 
 impl<T, F> AnyComputation for EffectState<T, F>
@@ -357,3 +453,38 @@ where
         true
     }
 }
+
+impl RuntimeId {
+    /// Sets whether effects of `phase` wait for [`run_phase`](RuntimeId::run_phase)
+    /// rather than running as soon as they are marked dirty.
+    ///
+    /// Whatever drives the runtime can defer the later phases, and run them
+    /// once per frame, so that layout and rendering only ever see the state
+    /// the frame ended with.
+    pub fn set_phase_deferred(self, phase: EffectPhase, deferred: bool) {
+        _ = with_runtime(|runtime| {
+            let mut deferred_phases = runtime.deferred_phases.borrow_mut();
+            if deferred {
+                deferred_phases.insert(phase);
+            } else {
+                deferred_phases.remove(&phase);
+            }
+        });
+    }
+
+    /// Runs every pending effect of `phase` and of the phases before it,
+    /// including deferred ones, until none are left.
+    ///
+    /// Effects of later phases that aren't deferred run too, so that what
+    /// the deferred effects write is reacted to before this returns.
+    pub fn run_phase(self, phase: EffectPhase) {
+        _ = with_runtime(|runtime| {
+            if !runtime.batching.get() {
+                let deferred_phases = runtime.deferred_phases.borrow().clone();
+                runtime.run_pending_effects(|pending| {
+                    pending <= phase || !deferred_phases.contains(&pending)
+                });
+            }
+        });
+    }
+}
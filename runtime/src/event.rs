@@ -0,0 +1,147 @@
+use crate::{on_cleanup, store_value, untrack, StoredValue};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+/// Sends one-shot events, such as "scroll to the top" or "show a toast", to
+/// the listeners of an [`EventSubscriber`]. Created with
+/// [`create_event_channel`].
+///
+/// Unlike a signal, an event has no current value: every value emitted is
+/// passed to every listener, even if several are emitted in one
+/// [`batch`](crate::batch), and then forgotten.
+pub struct EventEmitter<T: 'static> {
+    listeners: StoredValue<Listeners<T>>,
+}
+
+/// Listens to the events sent by an [`EventEmitter`]. Created with
+/// [`create_event_channel`].
+pub struct EventSubscriber<T: 'static> {
+    listeners: StoredValue<Listeners<T>>,
+}
+
+type Listener<T> = Rc<dyn Fn(&T)>;
+
+struct Listeners<T> {
+    listeners: RefCell<Vec<(usize, Listener<T>)>>,
+    next_id: Cell<usize>,
+}
+
+/// Creates a channel for one-shot events of type `T`, and returns the
+/// [`EventEmitter`] that sends them and the [`EventSubscriber`] that listens
+/// to them.
+///
+/// Both are `Copy`, so either can be shared across the app with
+/// [`provide_context`](crate::provide_context).
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # let runtime = create_runtime();
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Toast {
+///     Saved,
+///     Failed,
+/// }
+///
+/// let (toasts, on_toast) = create_event_channel::<Toast>();
+/// provide_context(toasts);
+///
+/// let shown = Rc::new(RefCell::new(Vec::new()));
+/// on_toast.subscribe({
+///     let shown = Rc::clone(&shown);
+///     move |toast| shown.borrow_mut().push(*toast)
+/// });
+///
+/// let toasts = use_context::<EventEmitter<Toast>>().unwrap();
+/// batch(|| {
+///     toasts.emit(Toast::Saved);
+///     toasts.emit(Toast::Failed);
+/// });
+/// assert_eq!(*shown.borrow(), [Toast::Saved, Toast::Failed]);
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_event_channel<T>() -> (EventEmitter<T>, EventSubscriber<T>) {
+    let listeners = store_value(Listeners {
+        listeners: RefCell::new(Vec::new()),
+        next_id: Cell::new(0),
+    });
+    (EventEmitter { listeners }, EventSubscriber { listeners })
+}
+
+impl<T> EventEmitter<T> {
+    /// Passes `value` to every listener, right away, in the order they
+    /// subscribed.
+    ///
+    /// Listeners are run untracked, so emitting from an effect doesn't make
+    /// it depend on whatever the listeners read. Nothing happens if the
+    /// channel has been disposed.
+    pub fn emit(&self, value: T) {
+        let listeners = self.listeners.try_with_value(|listeners| {
+            listeners
+                .listeners
+                .borrow()
+                .iter()
+                .map(|(_, listener)| Rc::clone(listener))
+                .collect::<Vec<_>>()
+        });
+        untrack(|| {
+            for listener in listeners.into_iter().flatten() {
+                listener(&value);
+            }
+        });
+    }
+}
+
+impl<T> EventSubscriber<T> {
+    /// Calls `f` with every value emitted from now on, until the current
+    /// owner is cleaned up.
+    pub fn subscribe(&self, f: impl Fn(&T) + 'static) {
+        let id = self.listeners.with_value(|listeners| {
+            let id = listeners.next_id.get();
+            listeners.next_id.set(id + 1);
+            listeners.listeners.borrow_mut().push((id, Rc::new(f)));
+            id
+        });
+        let listeners = self.listeners;
+        on_cleanup(move || {
+            _ = listeners.try_with_value(|listeners| {
+                listeners
+                    .listeners
+                    .borrow_mut()
+                    .retain(|(listener, _)| *listener != id);
+            });
+        });
+    }
+}
+
+impl<T> Clone for EventEmitter<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EventEmitter<T> {}
+
+impl<T> fmt::Debug for EventEmitter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventEmitter").finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for EventSubscriber<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for EventSubscriber<T> {}
+
+impl<T> fmt::Debug for EventSubscriber<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSubscriber").finish_non_exhaustive()
+    }
+}
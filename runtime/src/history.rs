@@ -0,0 +1,361 @@
+use crate::{
+    batch, create_isomorphic_effect, create_rw_signal, create_trigger, store_value,
+    timer::clock_now, RwSignal, Signal, SignalGet, SignalGetUntracked, SignalSet, StoredValue,
+    Trigger,
+};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// How a [`History`] groups changes into undo steps, and how many it keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryOptions {
+    /// The most undo steps to keep. Once there are more, the oldest ones are
+    /// forgotten. Defaults to no limit.
+    pub capacity: Option<usize>,
+    /// Changes made within this long of the previous one are added to the
+    /// same undo step, so that typing a word can be undone at once rather
+    /// than letter by letter. Defaults to no coalescing.
+    pub coalesce: Option<Duration>,
+}
+
+/// Creates a [`History`] that records the changes made to `signal`, so that
+/// they can be undone and redone.
+///
+/// `signal` can be anything that can be read and written, such as an
+/// [`RwSignal`] or a path into a [`Store`](crate::Store). More signals can be
+/// added to the same history with [`History::track`].
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let text = create_rw_signal(String::new());
+/// let history = create_history(text, HistoryOptions::default());
+///
+/// text.set("hello".to_string());
+/// text.set("hello world".to_string());
+/// assert!(history.can_undo().get());
+///
+/// history.undo();
+/// assert_eq!(text.get(), "hello");
+/// assert!(history.can_redo().get());
+///
+/// history.redo();
+/// assert_eq!(text.get(), "hello world");
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_history<S, T>(signal: S, options: HistoryOptions) -> History
+where
+    S: SignalGet<Value = T> + SignalGetUntracked<Value = T> + SignalSet<Value = T>,
+    S: Copy + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    let history = History::new(options);
+    history.track(signal);
+    history
+}
+
+/// Undo and redo for a group of signals, created with [`create_history`].
+///
+/// Every time the tracked signals change, the history records an undo step.
+/// Changes made inside a [`batch`] (or a
+/// [`transaction`](History::transaction)) are recorded as one step, even
+/// across several signals.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct History {
+    state: StoredValue<Rc<HistoryState>>,
+}
+
+struct HistoryState {
+    options: HistoryOptions,
+    tracked: RefCell<Vec<Box<dyn Tracked>>>,
+    /// Reruns the recording effect, so it subscribes to new signals.
+    retrack: Trigger,
+    undo: RefCell<VecDeque<Step>>,
+    redo: RefCell<Vec<Step>>,
+    /// The number of undo and redo steps.
+    lengths: RwSignal<(usize, usize)>,
+    /// `None` outside a transaction, and whether it has recorded a step yet
+    /// inside one.
+    transaction: Cell<Option<bool>>,
+    last_recorded: Cell<Option<Instant>>,
+}
+
+type Step = Vec<Box<dyn Change>>;
+
+/// A signal tracked by a [`History`], with the value it was last seen with.
+trait Tracked {
+    /// Returns how the signal changed since it was last seen, subscribing
+    /// to it.
+    fn diff(&self, index: usize) -> Option<Box<dyn Change>>;
+
+    /// Takes the current value as the one last seen.
+    fn resync(&self);
+}
+
+/// A change to one of the signals tracked by a [`History`].
+trait Change {
+    /// The index of the signal in [`HistoryState::tracked`].
+    fn index(&self) -> usize;
+
+    fn undo(&self);
+
+    fn redo(&self);
+
+    /// Takes on the result of a newer change to the same signal.
+    fn absorb(&mut self, newer: Box<dyn Change>);
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+struct TrackedSignal<S, T> {
+    signal: S,
+    snapshot: RefCell<T>,
+}
+
+struct SignalChange<S, T> {
+    index: usize,
+    signal: S,
+    before: T,
+    after: T,
+}
+
+impl<S, T> Tracked for TrackedSignal<S, T>
+where
+    S: SignalGet<Value = T> + SignalGetUntracked<Value = T> + SignalSet<Value = T>,
+    S: Copy + 'static,
+    T: Clone + PartialEq + 'static,
+{
+    fn diff(&self, index: usize) -> Option<Box<dyn Change>> {
+        let now = self.signal.try_get()?;
+        if now == *self.snapshot.borrow() {
+            return None;
+        }
+        let before = self.snapshot.replace(now.clone());
+        Some(Box::new(SignalChange {
+            index,
+            signal: self.signal,
+            before,
+            after: now,
+        }))
+    }
+
+    fn resync(&self) {
+        if let Some(now) = self.signal.try_get_untracked() {
+            *self.snapshot.borrow_mut() = now;
+        }
+    }
+}
+
+impl<S, T> Change for SignalChange<S, T>
+where
+    S: SignalSet<Value = T> + 'static,
+    T: Clone + 'static,
+{
+    fn index(&self) -> usize {
+        self.index
+    }
+
+    fn undo(&self) {
+        self.signal.try_set(self.before.clone());
+    }
+
+    fn redo(&self) {
+        self.signal.try_set(self.after.clone());
+    }
+
+    fn absorb(&mut self, newer: Box<dyn Change>) {
+        if let Ok(newer) = newer.into_any().downcast::<Self>() {
+            self.after = newer.after;
+        }
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl History {
+    /// Creates a [`History`] that doesn't track anything yet.
+    #[track_caller]
+    pub fn new(options: HistoryOptions) -> Self {
+        let state = Rc::new(HistoryState {
+            options,
+            tracked: Default::default(),
+            retrack: create_trigger(),
+            undo: Default::default(),
+            redo: Default::default(),
+            lengths: create_rw_signal((0, 0)),
+            transaction: Cell::new(None),
+            last_recorded: Cell::new(None),
+        });
+
+        create_isomorphic_effect({
+            let state = Rc::clone(&state);
+            move |_| {
+                state.retrack.track();
+                let step = state
+                    .tracked
+                    .borrow()
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, tracked)| tracked.diff(index))
+                    .collect::<Step>();
+                if !step.is_empty() {
+                    state.record(step);
+                }
+            }
+        });
+
+        Self {
+            state: store_value(state),
+        }
+    }
+
+    /// Records the changes made to `signal` in this history too.
+    pub fn track<S, T>(&self, signal: S)
+    where
+        S: SignalGet<Value = T> + SignalGetUntracked<Value = T> + SignalSet<Value = T>,
+        S: Copy + 'static,
+        T: Clone + PartialEq + 'static,
+    {
+        let Some(snapshot) = signal.try_get_untracked() else {
+            return;
+        };
+        let state = self.state();
+        state.tracked.borrow_mut().push(Box::new(TrackedSignal {
+            signal,
+            snapshot: RefCell::new(snapshot),
+        }));
+        state.retrack.notify();
+    }
+
+    /// Runs `f`, recording every change it makes as a single undo step.
+    pub fn transaction<U>(&self, f: impl FnOnce() -> U) -> U {
+        let state = self.state();
+        // a nested transaction belongs to the outer one's step
+        let outer = state.transaction.get();
+        if outer.is_none() {
+            state.transaction.set(Some(false));
+        }
+        let value = batch(f);
+        if outer.is_none() {
+            state.transaction.set(None);
+        }
+        value
+    }
+
+    /// Reverts the most recent undo step, if there is one.
+    pub fn undo(&self) {
+        let state = self.state();
+        let Some(step) = state.undo.borrow_mut().pop_back() else {
+            return;
+        };
+        state.apply(&step, |change| change.undo());
+        state.redo.borrow_mut().push(step);
+        state.update_lengths();
+    }
+
+    /// Reapplies the most recently undone step, if there is one.
+    pub fn redo(&self) {
+        let state = self.state();
+        let Some(step) = state.redo.borrow_mut().pop() else {
+            return;
+        };
+        state.apply(&step, |change| change.redo());
+        state.undo.borrow_mut().push_back(step);
+        state.update_lengths();
+    }
+
+    /// Whether there is anything to [`undo`](History::undo).
+    pub fn can_undo(&self) -> Signal<bool> {
+        let lengths = self.state().lengths;
+        Signal::derive(move || lengths.get().0 > 0)
+    }
+
+    /// Whether there is anything to [`redo`](History::redo).
+    pub fn can_redo(&self) -> Signal<bool> {
+        let lengths = self.state().lengths;
+        Signal::derive(move || lengths.get().1 > 0)
+    }
+
+    /// Forgets every undo and redo step, keeping the current values.
+    pub fn clear(&self) {
+        let state = self.state();
+        state.undo.borrow_mut().clear();
+        state.redo.borrow_mut().clear();
+        state.last_recorded.set(None);
+        state.update_lengths();
+    }
+
+    fn state(&self) -> Rc<HistoryState> {
+        self.state.with_value(Rc::clone)
+    }
+}
+
+impl HistoryState {
+    fn record(&self, step: Step) {
+        let now = clock_now();
+        let merge = match self.transaction.get() {
+            Some(started) => {
+                self.transaction.set(Some(true));
+                started
+            }
+            None => self.options.coalesce.is_some_and(|window| {
+                self.last_recorded
+                    .get()
+                    .is_some_and(|last| now.duration_since(last) <= window)
+            }),
+        };
+        self.last_recorded.set(Some(now));
+        self.redo.borrow_mut().clear();
+
+        let mut undo = self.undo.borrow_mut();
+        match undo.back_mut() {
+            Some(last) if merge => {
+                for change in step {
+                    match last.iter_mut().find(|old| old.index() == change.index()) {
+                        Some(old) => old.absorb(change),
+                        None => last.push(change),
+                    }
+                }
+            }
+            _ => {
+                undo.push_back(step);
+                if let Some(capacity) = self.options.capacity {
+                    while undo.len() > capacity {
+                        undo.pop_front();
+                    }
+                }
+            }
+        }
+        drop(undo);
+        self.update_lengths();
+    }
+
+    /// Applies the changes of a step, without recording them.
+    fn apply(&self, step: &Step, f: impl Fn(&dyn Change)) {
+        batch(|| {
+            for change in step {
+                f(change.as_ref());
+            }
+            // the recording effect runs once the batch is done, and should
+            // find nothing new
+            for tracked in self.tracked.borrow().iter() {
+                tracked.resync();
+            }
+        });
+        // the next change starts a new step
+        self.last_recorded.set(None);
+    }
+
+    fn update_lengths(&self) {
+        let lengths = (self.undo.borrow().len(), self.redo.borrow().len());
+        self.lengths.set(lengths);
+    }
+}
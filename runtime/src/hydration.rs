@@ -1,22 +1,40 @@
 #[cfg(all(feature = "hydrate", feature = "experimental-islands"))]
 use crate::Owner;
 use crate::{
-    runtime::PinnedFuture, suspense::StreamChunk, with_runtime, ResourceId, SuspenseContext,
+    batch, create_isomorphic_effect, runtime::PinnedFuture, with_runtime, AnyResource, ResourceId,
+    SuspenseContext,
 };
 use futures::stream::FuturesUnordered;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "experimental-islands")]
 use std::cell::Cell;
-use std::collections::{HashMap, HashSet, VecDeque};
-#[doc(hidden)]
-/// Hydration data and other context that is shared between the server
-/// and the client.
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    io::{self, BufRead, BufReader, Read, Write},
+    rc::Rc,
+    sync::mpsc,
+};
+
+/// The state a process shares with the process on the other side of a
+/// [`StreamChunk`] stream.
+///
+/// A backend process runs resources and streams their values with
+/// [`stream_resources`](SharedContext::stream_resources), and the UI process
+/// resolves its own resources from them with
+/// [`receive_resources`](SharedContext::receive_resources), instead of running
+/// them itself. Both processes have to create the resources in the same
+/// order, so that they get the same [`ResourceId`]s.
 pub struct SharedContext {
-    /// Resources that initially needed to resolve from the server.
+    /// Resources that are resolved by the other process.
     pub server_resources: HashSet<ResourceId>,
     /// Resources that have not yet resolved.
     pub pending_resources: HashSet<ResourceId>,
-    /// Resources that have already resolved.
+    /// Resources that have already resolved, but haven't been created yet.
     pub resolved_resources: HashMap<ResourceId, String>,
+    /// Sets the value of a resource that has been created, from a value
+    /// received from the other process.
+    pub(crate) resolvers: HashMap<ResourceId, Rc<dyn Fn(String)>>,
     /// Suspended fragments that have not yet resolved.
     pub pending_fragments: HashMap<String, FragmentData>,
     /// Suspense fragments that contain only local resources.
@@ -27,6 +45,25 @@ pub struct SharedContext {
     pub islands: HashMap<Owner, web_sys::HtmlElement>,
 }
 
+/// A message from a backend process to a UI process, sent by
+/// [`SharedContext::stream_resources`].
+///
+/// Chunks are written as JSON, one per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StreamChunk {
+    /// The resources the backend will send values for. This is always the
+    /// first chunk.
+    Pending(Vec<ResourceId>),
+    /// A new value of a resource, serialized with
+    /// [`Serializable`](crate::Serializable).
+    Resolved {
+        /// The resource the value is for.
+        id: ResourceId,
+        /// The serialized value.
+        value: String,
+    },
+}
+
 impl SharedContext {
     /// Returns IDs for all [`Resource`](crate::Resource)s found on any scope.
     #[cfg_attr(
@@ -57,25 +94,17 @@ impl SharedContext {
     }
 
     /// Registers the given [`SuspenseContext`](crate::SuspenseContext) with the current scope,
-    /// calling the `resolver` when its resources are all resolved.
+    /// so that its fragment is ready once its resources are all resolved.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
     )]
-    pub fn register_suspense(
-        context: SuspenseContext,
-        key: &str,
-        out_of_order_resolver: impl FnOnce() -> String + 'static,
-        in_order_resolver: impl FnOnce() -> VecDeque<StreamChunk> + 'static,
-    ) {
-        use crate::create_isomorphic_effect;
+    pub fn register_suspense(context: SuspenseContext, key: &str) {
         use futures::StreamExt;
 
         _ = with_runtime(|runtime| {
             let mut shared_context = runtime.shared_context.borrow_mut();
-            let (tx1, mut rx1) = futures::channel::mpsc::unbounded();
-            let (tx2, mut rx2) = futures::channel::mpsc::unbounded();
-            let (tx3, mut rx3) = futures::channel::mpsc::unbounded();
+            let (tx, mut rx) = futures::channel::mpsc::unbounded();
 
             create_isomorphic_effect(move |_| {
                 let pending = context
@@ -84,28 +113,16 @@ impl SharedContext {
                     .try_with(|n| *n)
                     .unwrap_or(0);
                 if pending == 0 {
-                    _ = tx1.unbounded_send(());
-                    _ = tx2.unbounded_send(());
-                    _ = tx3.unbounded_send(());
+                    _ = tx.unbounded_send(());
                 }
             });
 
             shared_context.pending_fragments.insert(
                 key.to_string(),
                 FragmentData {
-                    out_of_order: Box::pin(async move {
-                        rx1.next().await;
-
-                        out_of_order_resolver()
-                    }),
-                    in_order: Box::pin(async move {
-                        rx2.next().await;
-
-                        in_order_resolver()
-                    }),
                     should_block: context.should_block(),
                     is_ready: Some(Box::pin(async move {
-                        rx3.next().await;
+                        rx.next().await;
                     })),
                     local_only: context.has_local_only(),
                 },
@@ -113,10 +130,7 @@ impl SharedContext {
         })
     }
 
-    /// Takes the pending HTML for a single `<Suspense/>` node.
-    ///
-    /// Returns a tuple of two pinned `Future`s that return content for out-of-order
-    /// and in-order streaming, respectively.
+    /// Takes the data of a single pending fragment.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -154,10 +168,7 @@ impl SharedContext {
         Box::pin(async move { while ready.next().await.is_some() {} })
     }
 
-    /// The set of all HTML fragments currently pending.
-    ///
-    /// The keys are hydration IDs. Values are tuples of two pinned
-    /// `Future`s that return content for out-of-order and in-order streaming, respectively.
+    /// Takes every fragment currently pending, by key.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -186,6 +197,7 @@ impl SharedContext {
         }
     }
 
+    /// Whether `fragment` contains only local resources, forgetting it.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -200,6 +212,7 @@ impl SharedContext {
         .unwrap_or_default()
     }
 
+    /// Takes every fragment that contains only local resources.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -212,6 +225,7 @@ impl SharedContext {
         .unwrap_or_default()
     }
 
+    /// Marks the fragment `key` as containing only local resources.
     #[cfg_attr(
         any(debug_assertions, features = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -225,12 +239,9 @@ impl SharedContext {
     }
 }
 
-/// Represents its pending `<Suspense/>` fragment.
+/// A pending suspense fragment, registered with
+/// [`SharedContext::register_suspense`].
 pub struct FragmentData {
-    /// Future that represents how it should be render for an out-of-order stream.
-    pub out_of_order: PinnedFuture<String>,
-    /// Future that represents how it should be render for an in-order stream.
-    pub in_order: PinnedFuture<VecDeque<StreamChunk>>,
     /// Whether the stream should wait for this fragment before sending any data.
     pub should_block: bool,
     /// Future that will resolve when the fragment is ready.
@@ -239,6 +250,192 @@ pub struct FragmentData {
     pub local_only: bool,
 }
 
+impl SharedContext {
+    /// Streams the values of every resource created so far to `writer`, as
+    /// [`StreamChunk`]s, for a UI process to
+    /// [receive](SharedContext::receive_resources).
+    ///
+    /// The list of resources is written right away, followed by the value of
+    /// each one as soon as it has one, and again every time it changes, until
+    /// the current owner is cleaned up. If writing fails later on, the stream
+    /// is closed and nothing more is written.
+    ///
+    /// # Errors
+    /// Returns an error if the list of resources can't be written.
+    pub fn stream_resources(writer: impl Write + 'static) -> io::Result<()> {
+        let resources = with_runtime(|runtime| {
+            runtime
+                .resources
+                .borrow()
+                .iter()
+                .filter_map(|(id, resource)| match resource {
+                    AnyResource::Serializable(resource) if resource.should_send_to_client() => {
+                        Some((id, Rc::clone(resource)))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+        let mut writer = writer;
+        let ids = resources.iter().map(|(id, _)| *id).collect();
+        write_chunk(&mut writer, &StreamChunk::Pending(ids))?;
+
+        let writer = Rc::new(RefCell::new(Some(writer)));
+        for (id, resource) in resources {
+            let writer = Rc::clone(&writer);
+            resource.watch_serialized(Box::new(move |value| {
+                let mut writer = writer.borrow_mut();
+                let Some(w) = writer.as_mut() else {
+                    return;
+                };
+                if let Err(e) = write_chunk(w, &StreamChunk::Resolved { id, value }) {
+                    crate::macros::debug_warn!("closing the resource stream: {e}");
+                    *writer = None;
+                }
+            }));
+        }
+        Ok(())
+    }
+
+    /// Resolves resources from the [`StreamChunk`]s a backend process writes
+    /// to `reader` with [`stream_resources`](SharedContext::stream_resources),
+    /// instead of running them in this process.
+    ///
+    /// This blocks until the list of resources has been read, so it has to
+    /// be called before they are created. The rest of the stream is read on
+    /// a thread of its own, which calls `wake` every time a chunk arrives, so
+    /// that whatever drives the runtime knows to call
+    /// [`ResourceReceiver::resolve`].
+    ///
+    /// # Errors
+    /// Returns an error if the list of resources can't be read.
+    pub fn receive_resources(
+        reader: impl Read + Send + 'static,
+        wake: impl Fn() + Send + 'static,
+    ) -> io::Result<ResourceReceiver> {
+        let mut reader = BufReader::new(reader);
+        let Some(StreamChunk::Pending(ids)) = read_chunk(&mut reader)? else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the resource stream didn't start with the list of resources",
+            ));
+        };
+        resolve_chunk(StreamChunk::Pending(ids));
+
+        let (tx, chunks) = mpsc::channel();
+        std::thread::spawn(move || {
+            loop {
+                match read_chunk(&mut reader) {
+                    Ok(Some(chunk)) => {
+                        if tx.send(chunk).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        crate::macros::debug_warn!("closing the resource stream: {e}");
+                        break;
+                    }
+                }
+                wake();
+            }
+            // lets the receiver see that the stream is closed
+            drop(tx);
+            wake();
+        });
+        Ok(ResourceReceiver { chunks })
+    }
+}
+
+/// The receiving end of a resource stream, created with
+/// [`SharedContext::receive_resources`].
+#[derive(Debug)]
+pub struct ResourceReceiver {
+    chunks: mpsc::Receiver<StreamChunk>,
+}
+
+impl ResourceReceiver {
+    /// Resolves resources from every chunk received so far, in a single
+    /// [`batch`].
+    ///
+    /// Returns `false` once the stream has been closed, and everything in it
+    /// has been resolved.
+    pub fn resolve(&self) -> bool {
+        let mut chunks = Vec::new();
+        let open = loop {
+            match self.chunks.try_recv() {
+                Ok(chunk) => chunks.push(chunk),
+                Err(mpsc::TryRecvError::Empty) => break true,
+                Err(mpsc::TryRecvError::Disconnected) => break false,
+            }
+        };
+        batch(|| chunks.into_iter().for_each(resolve_chunk));
+        open
+    }
+
+    /// Blocks until the next chunk arrives, and then
+    /// [resolves](ResourceReceiver::resolve) everything received so far.
+    ///
+    /// Returns `false` if the stream has been closed.
+    pub fn wait(&self) -> bool {
+        match self.chunks.recv() {
+            Ok(chunk) => {
+                batch(|| resolve_chunk(chunk));
+                self.resolve()
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+fn write_chunk(writer: &mut impl Write, chunk: &StreamChunk) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, chunk)?;
+    writer.write_all(b"\n")?;
+    writer.flush()
+}
+
+fn read_chunk(reader: &mut impl BufRead) -> io::Result<Option<StreamChunk>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn resolve_chunk(chunk: StreamChunk) {
+    match chunk {
+        StreamChunk::Pending(ids) => {
+            _ = with_runtime(|runtime| {
+                let mut shared_context = runtime.shared_context.borrow_mut();
+                shared_context.server_resources.extend(ids.iter().copied());
+                shared_context.pending_resources.extend(ids);
+            });
+        }
+        StreamChunk::Resolved { id, value } => {
+            let resolver = with_runtime(|runtime| {
+                let mut shared_context = runtime.shared_context.borrow_mut();
+                match shared_context.resolvers.get(&id) {
+                    Some(resolver) => Some(Rc::clone(resolver)),
+                    // the resource takes it once it is created
+                    None => {
+                        shared_context.resolved_resources.insert(id, value.clone());
+                        None
+                    }
+                }
+            })
+            .ok()
+            .flatten();
+            if let Some(resolver) = resolver {
+                resolver(value);
+            }
+        }
+    }
+}
+
 impl std::fmt::Debug for SharedContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SharedContext").finish()
@@ -257,60 +454,17 @@ impl Eq for SharedContext {}
 #[allow(clippy::derivable_impls)]
 impl Default for SharedContext {
     fn default() -> Self {
-        #[cfg(all(feature = "hydrate", target_arch = "wasm32"))]
-        {
-            let pending_resources = js_sys::Reflect::get(
-                &web_sys::window().unwrap(),
-                &wasm_bindgen::JsValue::from_str("__LEPTOS_PENDING_RESOURCES"),
-            );
-            let pending_resources: HashSet<ResourceId> = pending_resources
-                .map_err(|_| ())
-                .and_then(|pr| serde_wasm_bindgen::from_value(pr).map_err(|_| ()))
-                .unwrap();
-            let fragments_with_local_resources = js_sys::Reflect::get(
-                &web_sys::window().unwrap(),
-                &wasm_bindgen::JsValue::from_str("__LEPTOS_LOCAL_ONLY"),
-            );
-            let fragments_with_local_resources: HashSet<String> = fragments_with_local_resources
-                .map_err(|_| ())
-                .and_then(|pr| serde_wasm_bindgen::from_value(pr).map_err(|_| ()))
-                .unwrap_or_default();
-
-            let resolved_resources = js_sys::Reflect::get(
-                &web_sys::window().unwrap(),
-                &wasm_bindgen::JsValue::from_str("__LEPTOS_RESOLVED_RESOURCES"),
-            )
-            .unwrap(); // unwrap_or(wasm_bindgen::JsValue::NULL);
-
-            let resolved_resources = serde_wasm_bindgen::from_value(resolved_resources).unwrap();
-
-            Self {
-                server_resources: pending_resources.clone(),
-                //events: Default::default(),
-                pending_resources,
-                resolved_resources,
-                fragments_with_local_resources,
-                pending_fragments: Default::default(),
-                #[cfg(feature = "experimental-islands")]
-                no_hydrate: true,
-                #[cfg(all(feature = "hydrate", feature = "experimental-islands"))]
-                islands: Default::default(),
-            }
-        }
-        #[cfg(not(all(feature = "hydrate", target_arch = "wasm32")))]
-        {
-            Self {
-                server_resources: Default::default(),
-                //events: Default::default(),
-                pending_resources: Default::default(),
-                resolved_resources: Default::default(),
-                pending_fragments: Default::default(),
-                fragments_with_local_resources: Default::default(),
-                #[cfg(feature = "experimental-islands")]
-                no_hydrate: true,
-                #[cfg(all(feature = "hydrate", feature = "experimental-islands"))]
-                islands: Default::default(),
-            }
+        Self {
+            server_resources: Default::default(),
+            pending_resources: Default::default(),
+            resolved_resources: Default::default(),
+            resolvers: Default::default(),
+            pending_fragments: Default::default(),
+            fragments_with_local_resources: Default::default(),
+            #[cfg(feature = "experimental-islands")]
+            no_hydrate: true,
+            #[cfg(all(feature = "hydrate", feature = "experimental-islands"))]
+            islands: Default::default(),
         }
     }
 }
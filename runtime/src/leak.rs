@@ -0,0 +1,246 @@
+//! Lists the reactive values that are still alive, so that leaks can be
+//! tracked down to the place they were created.
+
+use crate::{
+    node::{NodeId, ReactiveNodeType},
+    with_runtime, Owner, Runtime, RuntimeId, ScopeProperty,
+};
+use rustc_hash::FxHashSet;
+use std::{fmt, panic::Location};
+
+/// The kind of a reactive value, as reported by [`AliveValue`] and
+/// [`ReactiveSystemError::Disposed`](crate::ReactiveSystemError::Disposed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// A [`Trigger`](crate::Trigger), or an owner created by
+    /// [`run_as_child`](crate::run_as_child).
+    Trigger,
+    /// A signal, such as a [`ReadSignal`](crate::ReadSignal) or an
+    /// [`RwSignal`](crate::RwSignal).
+    Signal,
+    /// A [`Memo`](crate::Memo).
+    Memo,
+    /// An [`Effect`](crate::Effect).
+    Effect,
+    /// An owner created by
+    /// [`as_child_of_current_owner`](crate::as_child_of_current_owner) whose
+    /// [`Disposer`](crate::Disposer) has not been dropped yet.
+    Child,
+    /// A [`Resource`](crate::Resource).
+    Resource,
+    /// A [`StoredValue`](crate::StoredValue).
+    StoredValue,
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Trigger => "trigger",
+            Self::Signal => "signal",
+            Self::Memo => "memo",
+            Self::Effect => "effect",
+            Self::Child => "child owner",
+            Self::Resource => "resource",
+            Self::StoredValue => "stored value",
+        })
+    }
+}
+
+/// A reactive value that has not been disposed yet.
+///
+/// Returned by [`Owner::alive_values`] and [`RuntimeId::alive_values`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AliveValue {
+    /// What kind of value this is.
+    pub kind: ValueKind,
+    /// Where the value was created. This is only tracked in debug mode.
+    pub defined_at: Option<&'static Location<'static>>,
+    /// The owner the value will be disposed with, if any.
+    pub owner: Option<Owner>,
+}
+
+impl fmt::Display for AliveValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.defined_at {
+            Some(defined_at) => write!(f, "{} created at {defined_at}", self.kind),
+            None => write!(f, "{} (created at an unknown location)", self.kind),
+        }
+    }
+}
+
+impl Owner {
+    /// Lists every node, resource and stored value that is still alive under
+    /// this owner, including the ones owned by its children, in the order
+    /// they were created.
+    ///
+    /// ```
+    /// # use goober_runtime::*;
+    /// # let runtime = create_runtime();
+    /// let (child, disposer) = as_child_of_current_owner(|_: ()| {
+    ///     store_value(0);
+    ///     Owner::current().unwrap()
+    /// })(());
+    ///
+    /// assert_eq!(child.alive_values()[0].kind, ValueKind::StoredValue);
+    /// drop(disposer);
+    /// assert!(child.alive_values().is_empty());
+    /// # runtime.dispose();
+    /// ```
+    pub fn alive_values(&self) -> Vec<AliveValue> {
+        with_runtime(|runtime| {
+            let mut values = Vec::new();
+            runtime.collect_alive_values(self.0, &mut values, &mut FxHashSet::default());
+            values
+        })
+        .unwrap_or_default()
+    }
+}
+
+impl RuntimeId {
+    /// Lists every node, resource and stored value that is still alive in the
+    /// runtime, including the ones that were created without an owner.
+    ///
+    /// Calling this before disposing of the runtime, or after disposing of a
+    /// part of the application, is a quick way to find out what is leaking
+    /// and where it was created.
+    pub fn alive_values(self) -> Vec<AliveValue> {
+        with_runtime(|runtime| {
+            let mut values = Vec::new();
+            let mut seen = FxHashSet::default();
+            runtime.collect_alive_values(runtime.root, &mut values, &mut seen);
+
+            // anything that was created outside of any owner
+            let unowned = runtime
+                .all_properties()
+                .into_iter()
+                .filter(|property| !seen.contains(property))
+                .collect::<Vec<_>>();
+            for property in unowned {
+                values.extend(runtime.alive_value(property, None));
+            }
+            values
+        })
+        .unwrap_or_default()
+    }
+
+    /// Warns about every child created with
+    /// [`as_child_of_current_owner`](crate::as_child_of_current_owner) that is
+    /// still alive, and forgets about them. These have either had their
+    /// [`Disposer`](crate::Disposer) forgotten, or are being held on to by
+    /// something that outlives the runtime.
+    pub(crate) fn warn_leaked_children(self) {
+        #[cfg(debug_assertions)]
+        for child in self
+            .alive_values()
+            .into_iter()
+            .filter(|value| value.kind == ValueKind::Child)
+        {
+            crate::macros::debug_warn!(
+                "The {child} is still alive while the runtime is being \
+                 disposed, which means its `Disposer` was never dropped."
+            );
+        }
+        _ = with_runtime(|runtime| runtime.child_nodes.borrow_mut().clear());
+    }
+}
+
+impl Runtime {
+    /// Walks the properties of `owner` depth-first, pushing every one that is
+    /// still alive to `values`.
+    fn collect_alive_values(
+        &self,
+        owner: NodeId,
+        values: &mut Vec<AliveValue>,
+        seen: &mut FxHashSet<ScopeProperty>,
+    ) {
+        let properties = self.node_properties.borrow().get(owner).cloned();
+        for property in properties.into_iter().flatten() {
+            if !seen.insert(property) {
+                continue;
+            }
+            if let Some(value) = self.alive_value(property, Some(Owner(owner))) {
+                values.push(value);
+                if let Some(node) = property.to_node_id() {
+                    self.collect_alive_values(node, values, seen);
+                }
+            }
+        }
+    }
+
+    /// Describes the given property, or returns `None` if it has already
+    /// been disposed.
+    fn alive_value(&self, property: ScopeProperty, owner: Option<Owner>) -> Option<AliveValue> {
+        let (kind, defined_at) = match property {
+            ScopeProperty::Trigger(node)
+            | ScopeProperty::Signal(node)
+            | ScopeProperty::Effect(node) => {
+                let nodes = self.nodes.borrow();
+                let node_data = nodes.get(node)?;
+                let kind = match node_data.node_type {
+                    ReactiveNodeType::Trigger if self.child_nodes.borrow().contains(&node) => {
+                        ValueKind::Child
+                    }
+                    ReactiveNodeType::Trigger => ValueKind::Trigger,
+                    ReactiveNodeType::Signal => ValueKind::Signal,
+                    ReactiveNodeType::Memo { .. } => ValueKind::Memo,
+                    ReactiveNodeType::Effect { .. } => ValueKind::Effect,
+                };
+                #[cfg(debug_assertions)]
+                let defined_at = Some(node_data.defined_at);
+                #[cfg(not(debug_assertions))]
+                let defined_at = None;
+                (kind, defined_at)
+            }
+            ScopeProperty::Resource(id) => {
+                if !self.resources.borrow().contains_key(id) {
+                    return None;
+                }
+                #[cfg(debug_assertions)]
+                let defined_at = self.resource_locations.borrow().get(id).copied();
+                #[cfg(not(debug_assertions))]
+                let defined_at = None;
+                (ValueKind::Resource, defined_at)
+            }
+            ScopeProperty::StoredValue(id) => {
+                if !self.stored_values.borrow().contains_key(id) {
+                    return None;
+                }
+                #[cfg(debug_assertions)]
+                let defined_at = self.stored_value_locations.borrow().get(id).copied();
+                #[cfg(not(debug_assertions))]
+                let defined_at = None;
+                (ValueKind::StoredValue, defined_at)
+            }
+        };
+        Some(AliveValue {
+            kind,
+            defined_at,
+            owner,
+        })
+    }
+
+    /// Returns a property for everything that is alive in the runtime,
+    /// except for its root.
+    fn all_properties(&self) -> Vec<ScopeProperty> {
+        let nodes = self.nodes.borrow();
+        let mut properties = nodes
+            .iter()
+            .filter(|(id, _)| *id != self.root)
+            .map(|(id, node)| match node.node_type {
+                ReactiveNodeType::Trigger => ScopeProperty::Trigger(id),
+                ReactiveNodeType::Signal => ScopeProperty::Signal(id),
+                ReactiveNodeType::Memo { .. } | ReactiveNodeType::Effect { .. } => {
+                    ScopeProperty::Effect(id)
+                }
+            })
+            .collect::<Vec<_>>();
+        properties.extend(self.resources.borrow().keys().map(ScopeProperty::Resource));
+        properties.extend(
+            self.stored_values
+                .borrow()
+                .keys()
+                .map(ScopeProperty::StoredValue),
+        );
+        properties
+    }
+}
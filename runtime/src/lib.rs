@@ -80,12 +80,20 @@ extern crate tracing;
 
 #[macro_use]
 mod signal;
+mod action;
+mod async_memo;
 pub mod callback;
+mod channel;
+mod collections;
+mod combinators;
 mod context;
 #[macro_use]
 mod diagnostics;
 mod effect;
+mod event;
+mod history;
 mod hydration;
+mod leak;
 // contains "private" implementation details right now.
 // could make this unhidden in the future if needed.
 // macro_export makes it public from the crate root anyways
@@ -94,49 +102,81 @@ pub mod macros;
 mod memo;
 mod node;
 pub mod oco;
+mod persist;
+mod query;
+mod reactive_struct;
 mod resource;
 mod runtime;
 mod selector;
+mod send_signal;
 #[cfg(any(doc, feature = "serde"))]
 mod serde;
 mod serialization;
 mod signal_wrappers_read;
 mod signal_wrappers_write;
 mod slice;
+mod snapshot;
 mod spawn;
 mod spawn_microtask;
+mod store;
 mod stored_value;
+mod stream_resource;
 pub mod suspense;
+pub mod testing;
+mod timer;
 mod trigger;
 mod watch;
 
+pub use action::*;
+pub use async_memo::*;
 pub use callback::*;
+pub use channel::*;
+pub use collections::*;
+pub use combinators::SignalExt;
 pub use context::*;
 pub use diagnostics::SpecialNonReactiveZone;
 pub use effect::*;
+pub use event::*;
 pub use futures;
-pub use hydration::{FragmentData, SharedContext};
+pub use goober_macros::Reactive;
+pub use history::*;
+pub use hydration::{FragmentData, ResourceReceiver, SharedContext, StreamChunk};
+pub use leak::{AliveValue, ValueKind};
 pub use memo::*;
 pub use node::Disposer;
 pub use oco::*;
+pub use persist::*;
+pub use query::*;
+pub use reactive_struct::Reactive;
 pub use resource::*;
 use runtime::*;
 pub use runtime::{
     as_child_of_current_owner, batch, create_runtime, current_runtime, on_cleanup, run_as_child,
     spawn_local_with_current_owner, spawn_local_with_owner, try_spawn_local_with_current_owner,
     try_spawn_local_with_owner, try_with_owner, untrack, untrack_with_diagnostics,
-    with_current_owner, with_owner, Owner, RuntimeId, ScopedFuture,
+    with_current_owner, with_owner, Owner, ReactiveSystemError, RuntimeId, ScopedFuture,
+    DEFAULT_MAX_EFFECT_RERUNS,
 };
 pub use selector::*;
+pub use send_signal::{create_sync_setter, SendSignal};
 pub use serialization::*;
 pub use signal::{prelude as signal_prelude, *};
 pub use signal_wrappers_read::*;
 pub use signal_wrappers_write::*;
 pub use slice::*;
+pub use snapshot::{register_signal, Snapshot};
 pub use spawn::*;
 pub use spawn_microtask::*;
+pub use store::*;
 pub use stored_value::*;
-pub use suspense::{GlobalSuspenseContext, SuspenseContext};
+pub use stream_resource::*;
+pub use suspense::{
+    create_transition, start_transition, GlobalSuspenseContext, SuspenseContext, Transition,
+};
+pub use timer::{
+    create_interval, debounced, now, set_timeout, set_timeout_with_handle, throttled, TestClock,
+    TimerHandle,
+};
 pub use trigger::*;
 pub use watch::*;
 
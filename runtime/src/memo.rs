@@ -85,7 +85,97 @@ pub fn create_memo<T>(f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
 where
     T: PartialEq + 'static,
 {
-    Runtime::current().create_memo(f)
+    Runtime::current().create_memo(f, T::eq, false)
+}
+
+/// Creates a memo that uses `eq`, rather than [`PartialEq`], to decide
+/// whether a new value is the same as the old one, in which case its
+/// dependents aren't notified.
+///
+/// This lets a memo hold types that aren't [`PartialEq`], compare large
+/// values more cheaply (for example by length or version), or compare
+/// [`Rc`]s by identity. Pass [`always_notify`] to notify every time the memo
+/// is recomputed.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::rc::Rc;
+/// # let runtime = create_runtime();
+/// let (items, set_items) = create_signal(vec![1, 2, 3]);
+/// let shared = create_memo_with(Rc::ptr_eq, move |_| Rc::new(items.get()));
+///
+/// let first = shared.get();
+/// set_items.set(vec![1, 2, 3]);
+/// // an equal `Vec`, but a new `Rc`
+/// assert!(!Rc::ptr_eq(&first, &shared.get()));
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+#[inline(always)]
+pub fn create_memo_with<T>(
+    eq: impl Fn(&T, &T) -> bool + 'static,
+    f: impl Fn(Option<&T>) -> T + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+{
+    Runtime::current().create_memo(f, eq, false)
+}
+
+/// An equality for [`create_memo_with`] that never considers two values the
+/// same, so the memo notifies its dependents every time it is recomputed.
+pub fn always_notify<T>(_: &T, _: &T) -> bool {
+    false
+}
+
+/// Creates a memo that drops its value, and stops tracking what it depends
+/// on, as soon as its last subscriber stops subscribing to it.
+///
+/// Like any memo, it is only computed when it is read, but it doesn't hold
+/// on to a large value, or keep its sources busy marking it stale, while
+/// nothing is using it. Reading it again recomputes it from scratch, with
+/// `None` as the previous value.
+///
+/// A memo that is only ever read untracked never has a subscriber to lose,
+/// so it keeps its value like any other memo.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # let runtime = create_runtime();
+/// let runs = Rc::new(Cell::new(0));
+/// let (count, set_count) = create_signal(1);
+/// let squared = create_lazy_memo({
+///     let runs = Rc::clone(&runs);
+///     move |_| {
+///         runs.set(runs.get() + 1);
+///         count.get() * count.get()
+///     }
+/// });
+///
+/// let (show, set_show) = create_signal(true);
+/// create_isomorphic_effect(move |_| {
+///     if show.get() {
+///         squared.track();
+///     }
+/// });
+/// assert_eq!(runs.get(), 1);
+///
+/// // nothing subscribes to it now, so it isn't even marked stale
+/// set_show.set(false);
+/// set_count.set(2);
+/// assert_eq!(runs.get(), 1);
+/// assert_eq!(squared.get(), 4);
+/// assert_eq!(runs.get(), 2);
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+#[inline(always)]
+pub fn create_lazy_memo<T>(f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
+where
+    T: PartialEq + 'static,
+{
+    Runtime::current().create_memo(f, T::eq, true)
 }
 
 /// An efficient derived reactive value based on other reactive values.
@@ -353,9 +443,13 @@ impl<T> SignalWithUntracked for Memo<T> {
     )]
     #[inline]
     fn try_with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> Option<O> {
-        with_runtime(|runtime| self.id.try_with_no_subscription(runtime, |v: &T| f(v)).ok())
-            .ok()
-            .flatten()
+        with_runtime(|runtime| {
+            self.id
+                .try_with_no_subscription(runtime, forward_ref_to(f))
+                .ok()
+        })
+        .ok()
+        .flatten()
     }
 }
 
@@ -512,21 +606,28 @@ impl<T> SignalDispose for Memo<T> {
 
 impl_get_fn_traits![Memo];
 
-pub(crate) struct MemoState<T, F>
+pub(crate) struct MemoState<T, F, E>
 where
-    T: PartialEq + 'static,
+    T: 'static,
     F: Fn(Option<&T>) -> T,
+    E: Fn(&T, &T) -> bool,
 {
     pub f: F,
+    /// Whether a new value is the same as the old one, so that subscribers
+    /// don't need to be notified.
+    pub eq: E,
+    /// Whether the value is dropped once nothing subscribes to the memo.
+    pub lazy: bool,
     pub t: PhantomData<T>,
     #[cfg(any(debug_assertions, feature = "ssr"))]
     pub(crate) defined_at: &'static std::panic::Location<'static>,
 }
 
-impl<T, F> AnyComputation for MemoState<T, F>
+impl<T, F, E> AnyComputation for MemoState<T, F, E>
 where
-    T: PartialEq + 'static,
+    T: 'static,
     F: Fn(Option<&T>) -> T,
+    E: Fn(&T, &T) -> bool,
 {
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -549,7 +650,9 @@ where
 
             // run the effect
             let new_value = (self.f)(curr_value.as_ref());
-            let is_different = curr_value.as_ref() != Some(&new_value);
+            let is_different = !curr_value
+                .as_ref()
+                .is_some_and(|curr_value| (self.eq)(curr_value, &new_value));
             (new_value, is_different)
         };
         if is_different {
@@ -562,6 +665,16 @@ where
 
         is_different
     }
+
+    fn release(&self, value: Rc<RefCell<dyn Any>>) -> bool {
+        if self.lazy {
+            *value
+                .borrow_mut()
+                .downcast_mut::<Option<T>>()
+                .expect("to downcast memo value") = None;
+        }
+        self.lazy
+    }
 }
 
 #[cold]
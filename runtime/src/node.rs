@@ -14,6 +14,7 @@ impl Drop for Disposer {
     fn drop(&mut self) {
         let id = self.0;
         _ = with_runtime(|runtime| {
+            runtime.child_nodes.borrow_mut().swap_remove(&id);
             runtime.cleanup_node(id);
             runtime.dispose_node(id);
         });
@@ -25,6 +26,8 @@ pub(crate) struct ReactiveNode {
     pub value: Option<Rc<RefCell<dyn Any>>>,
     pub state: ReactiveNodeState,
     pub node_type: ReactiveNodeType,
+    #[cfg(debug_assertions)]
+    pub defined_at: &'static std::panic::Location<'static>,
 }
 
 impl ReactiveNode {
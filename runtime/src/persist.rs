@@ -0,0 +1,315 @@
+use crate::{
+    create_isomorphic_effect, create_rw_signal, on_cleanup,
+    serialization::Serializable,
+    timer::{set_timer, TimerHandle},
+    use_context, RwSignal, SignalWith, SignalWithUntracked,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::Duration,
+};
+
+/// A key-value store that persisted signals are saved in, as the strings
+/// produced by [`Serializable::ser`].
+pub trait PersistBackend {
+    /// Returns the value saved under `key`, if there is one.
+    fn load(&self, key: &str) -> io::Result<Option<String>>;
+
+    /// Saves `value` under `key`, replacing the previous value.
+    fn save(&self, key: &str, value: &str) -> io::Result<()>;
+}
+
+/// Keeps every value in a single JSON file, as an object from keys to
+/// values.
+#[derive(Debug)]
+pub struct JsonFileBackend {
+    path: PathBuf,
+    /// The contents of the file, once it has been read.
+    cache: RefCell<Option<BTreeMap<String, String>>>,
+}
+
+impl JsonFileBackend {
+    /// Uses the file at `path`, which is created on the first save.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cache: RefCell::new(None),
+        }
+    }
+
+    fn with_entries<U>(&self, f: impl FnOnce(&mut BTreeMap<String, String>) -> U) -> io::Result<U> {
+        let mut cache = self.cache.borrow_mut();
+        let entries = match &mut *cache {
+            Some(entries) => entries,
+            None => {
+                let entries = match fs::read_to_string(&self.path) {
+                    Ok(contents) => serde_json::from_str(&contents)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+                    Err(e) => return Err(e),
+                };
+                cache.insert(entries)
+            }
+        };
+        Ok(f(entries))
+    }
+}
+
+impl PersistBackend for JsonFileBackend {
+    fn load(&self, key: &str) -> io::Result<Option<String>> {
+        self.with_entries(|entries| entries.get(key).cloned())
+    }
+
+    fn save(&self, key: &str, value: &str) -> io::Result<()> {
+        let contents = self.with_entries(|entries| {
+            entries.insert(key.to_string(), value.to_string());
+            serde_json::to_string_pretty(entries)
+        })?;
+        write_atomically(&self.path, contents?.as_bytes())
+    }
+}
+
+/// Keeps each value in its own file in a directory, named after its key.
+#[derive(Debug, Clone)]
+pub struct DirectoryBackend {
+    dir: PathBuf,
+}
+
+impl DirectoryBackend {
+    /// Uses the directory at `dir`, which is created on the first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        // keys can't escape the directory, or clash with each other
+        let name = key
+            .bytes()
+            .map(|byte| match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{byte:02X}"),
+            })
+            .collect::<String>();
+        self.dir.join(name)
+    }
+}
+
+impl PersistBackend for DirectoryBackend {
+    fn load(&self, key: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(self.path(key)) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self, key: &str, value: &str) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        write_atomically(&self.path(key), value.as_bytes())
+    }
+}
+
+/// Writes to a temporary file first, so that a crash never leaves a value
+/// half written.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// Where [`create_persisted_signal`] loads and saves values, provided with
+/// [`provide_context`](crate::provide_context).
+///
+/// Saving waits until a signal has stopped changing for the
+/// [debounce](Persistence::with_debounce) time, using the runtime's timers,
+/// so it only happens if whatever drives the runtime calls
+/// [`RuntimeId::run_timers`](crate::RuntimeId::run_timers). Anything still
+/// waiting is saved when the signal is disposed, or by
+/// [`flush`](Persistence::flush).
+#[derive(Clone)]
+pub struct Persistence {
+    inner: Rc<PersistenceInner>,
+}
+
+struct PersistenceInner {
+    backend: Box<dyn PersistBackend>,
+    debounce: Cell<Duration>,
+    /// The saves waiting for their debounce time to pass, by key.
+    pending: RefCell<HashMap<String, PendingSave>>,
+}
+
+struct PendingSave {
+    timer: Option<TimerHandle>,
+    save: Rc<dyn Fn()>,
+}
+
+impl Persistence {
+    /// Saves values in `backend`, with a debounce time of 250 milliseconds.
+    pub fn new(backend: impl PersistBackend + 'static) -> Self {
+        Self {
+            inner: Rc::new(PersistenceInner {
+                backend: Box::new(backend),
+                debounce: Cell::new(Duration::from_millis(250)),
+                pending: Default::default(),
+            }),
+        }
+    }
+
+    /// Sets how long a signal has to stay unchanged before it is saved.
+    pub fn with_debounce(self, debounce: Duration) -> Self {
+        self.inner.debounce.set(debounce);
+        self
+    }
+
+    /// The backend values are saved in.
+    pub fn backend(&self) -> &dyn PersistBackend {
+        &*self.inner.backend
+    }
+
+    /// Saves every value that is waiting for its debounce time to pass.
+    pub fn flush(&self) {
+        let pending = std::mem::take(&mut *self.inner.pending.borrow_mut());
+        for (_, pending) in pending {
+            pending.run();
+        }
+    }
+
+    fn flush_key(&self, key: &str) {
+        let pending = self.inner.pending.borrow_mut().remove(key);
+        if let Some(pending) = pending {
+            pending.run();
+        }
+    }
+
+    fn schedule(&self, key: &str, save: Rc<dyn Fn()>) {
+        let timer = set_timer(self.inner.debounce.get(), {
+            let this = self.clone();
+            let key = key.to_string();
+            move || {
+                let pending = this.inner.pending.borrow_mut().remove(&key);
+                if let Some(pending) = pending {
+                    (pending.save)();
+                }
+            }
+        });
+        let previous = self
+            .inner
+            .pending
+            .borrow_mut()
+            .insert(key.to_string(), PendingSave { timer, save });
+        if let Some(PendingSave {
+            timer: Some(timer), ..
+        }) = previous
+        {
+            timer.cancel();
+        }
+    }
+}
+
+impl PendingSave {
+    fn run(self) {
+        if let Some(timer) = self.timer {
+            timer.cancel();
+        }
+        (self.save)();
+    }
+}
+
+/// Creates a signal whose value is saved under `key` in the [`Persistence`]
+/// provided in the current context, so that it survives restarts.
+///
+/// The signal starts with the saved value, or with `default` if there is
+/// none, or if it can't be deserialized (such as after the type has
+/// changed). Every change is saved once the signal has stopped changing for
+/// a moment.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// # let dir = std::env::temp_dir().join(format!("goober-doc-{}", std::process::id()));
+/// provide_context(Persistence::new(DirectoryBackend::new(&dir)));
+///
+/// let zoom = create_persisted_signal("zoom", 1.0);
+/// zoom.set(1.5);
+/// expect_context::<Persistence>().flush();
+///
+/// // the next time the app starts
+/// let zoom = create_persisted_signal("zoom", 1.0);
+/// assert_eq!(zoom.get(), 1.5);
+/// # runtime.dispose();
+/// # std::fs::remove_dir_all(dir).unwrap();
+/// ```
+///
+/// # Panics
+/// Panics if no [`Persistence`] has been provided.
+#[track_caller]
+pub fn create_persisted_signal<T>(key: impl Into<String>, default: T) -> RwSignal<T>
+where
+    T: Serializable + 'static,
+{
+    let key = key.into();
+    let Some(persistence) = use_context::<Persistence>() else {
+        panic!(
+            "create_persisted_signal({key:?}) was called without a Persistence \
+             provided in the context."
+        );
+    };
+
+    let initial = match persistence.backend().load(&key) {
+        Ok(Some(saved)) => match T::de(&saved) {
+            Ok(value) => value,
+            Err(e) => {
+                crate::macros::debug_warn!(
+                    "could not deserialize the value saved under {key:?}, so the \
+                     default is used instead: {e}"
+                );
+                default
+            }
+        },
+        Ok(None) => default,
+        Err(e) => {
+            crate::macros::debug_warn!("could not load the value saved under {key:?}: {e}");
+            default
+        }
+    };
+    let signal = create_rw_signal(initial);
+
+    let save: Rc<dyn Fn()> = {
+        let persistence = persistence.clone();
+        let key = key.clone();
+        Rc::new(move || {
+            let Some(serialized) = signal.try_with_untracked(T::ser) else {
+                return;
+            };
+            let result = serialized
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+                .and_then(|serialized| persistence.backend().save(&key, &serialized));
+            if let Err(e) = result {
+                crate::macros::debug_warn!("could not save the value of {key:?}: {e}");
+            }
+        })
+    };
+
+    create_isomorphic_effect({
+        let persistence = persistence.clone();
+        let key = key.clone();
+        move |prev: Option<()>| {
+            signal.with(|_| ());
+            // the first run only subscribes, since nothing has changed yet
+            if prev.is_some() {
+                persistence.schedule(&key, Rc::clone(&save));
+            }
+        }
+    });
+    // the signal can't change once it is gone, so there's no point waiting
+    on_cleanup(move || persistence.flush_key(&key));
+
+    signal
+}
@@ -0,0 +1,686 @@
+use crate::{
+    batch, create_isomorphic_effect, create_memo, create_rw_signal, expect_context, on_cleanup,
+    spawn::spawn_local,
+    suspense::Suspended,
+    timer::{clock_now, set_timer},
+    with_owner, Memo, Owner, RwSignal, Signal, SignalDispose, SignalGet, SignalGetUntracked,
+    SignalSet, SignalWith, SignalWithUntracked, TimerHandle,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    time::{Duration, Instant},
+};
+
+/// The key a query is cached under: a list of segments, such as
+/// `["users", "42", "posts"]`.
+///
+/// Queries can be invalidated by prefix with [`QueryClient::invalidate`], so
+/// the segments should go from the most general to the most specific.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct QueryKey(Vec<String>);
+
+impl QueryKey {
+    /// Creates a key with no segments, which is a prefix of every key.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the segments of `key` to this key.
+    pub fn push(&mut self, key: &(impl ToQueryKey + ?Sized)) {
+        self.0.extend(key.to_query_key().0);
+    }
+
+    /// Returns the segments of this key.
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Returns `true` if the first segments of this key are those of `prefix`.
+    pub fn starts_with(&self, prefix: &QueryKey) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+}
+
+impl fmt::Display for QueryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+/// Converts a value into the [`QueryKey`] it is cached under.
+///
+/// Strings, numbers, `bool` and `char` are a single segment. Tuples, slices
+/// and `Vec`s are the segments of their items, one after the other.
+pub trait ToQueryKey {
+    /// Returns the key for this value.
+    fn to_query_key(&self) -> QueryKey;
+}
+
+impl ToQueryKey for QueryKey {
+    fn to_query_key(&self) -> QueryKey {
+        self.clone()
+    }
+}
+
+impl<T: ToQueryKey + ?Sized> ToQueryKey for &T {
+    fn to_query_key(&self) -> QueryKey {
+        (**self).to_query_key()
+    }
+}
+
+impl<T: ToQueryKey> ToQueryKey for [T] {
+    fn to_query_key(&self) -> QueryKey {
+        let mut key = QueryKey::new();
+        for item in self {
+            key.push(item);
+        }
+        key
+    }
+}
+
+impl<T: ToQueryKey, const N: usize> ToQueryKey for [T; N] {
+    fn to_query_key(&self) -> QueryKey {
+        self.as_slice().to_query_key()
+    }
+}
+
+impl<T: ToQueryKey> ToQueryKey for Vec<T> {
+    fn to_query_key(&self) -> QueryKey {
+        self.as_slice().to_query_key()
+    }
+}
+
+macro_rules! impl_segment {
+    ($($ty:ty),*) => {
+        $(
+            impl ToQueryKey for $ty {
+                fn to_query_key(&self) -> QueryKey {
+                    QueryKey(vec![self.to_string()])
+                }
+            }
+        )*
+    };
+}
+
+impl_segment!(
+    str, String, bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+macro_rules! impl_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: ToQueryKey),*> ToQueryKey for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn to_query_key(&self) -> QueryKey {
+                let ($($name,)*) = self;
+                let mut key = QueryKey::new();
+                $(key.push($name);)*
+                key
+            }
+        }
+    };
+}
+
+impl_tuple!(A);
+impl_tuple!(A, B);
+impl_tuple!(A, B, C);
+impl_tuple!(A, B, C, D);
+impl_tuple!(A, B, C, D, E);
+impl_tuple!(A, B, C, D, E, F);
+
+/// How long the entries of a [`QueryClient`] are considered fresh, and how
+/// long they are kept once nothing uses them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// How long a fetched value is considered fresh. A query that starts
+    /// using a stale entry refetches it in the background, while still
+    /// seeing the cached value. Defaults to zero, so that every new query
+    /// revalidates.
+    pub stale_time: Duration,
+    /// How long an entry is kept after its last query has been disposed,
+    /// before it is removed. Defaults to five minutes.
+    pub gc_time: Duration,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            stale_time: Duration::ZERO,
+            gc_time: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// A cache of the values of async queries, shared by every [`use_query`]
+/// below the owner it was provided in with
+/// [`provide_context`](crate::provide_context).
+///
+/// Queries with the same key share one entry: it is fetched once, no matter
+/// how many queries use it, and all of them see the same value. Once an entry
+/// has been fetched, queries that start using it get the cached value
+/// immediately, and refetch it in the background if it is stale
+/// (stale-while-revalidate).
+///
+/// Entries that no queries use any more are removed once their
+/// [`gc_time`](QueryOptions::gc_time) has passed. This runs on the runtime's
+/// timers, so it only happens if whatever drives the runtime calls
+/// [`RuntimeId::run_timers`](crate::RuntimeId::run_timers), and can also be
+/// done manually with [`collect_garbage`](QueryClient::collect_garbage).
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::Cell, rc::Rc, time::Duration};
+/// # let runtime = create_runtime();
+/// # let tokio = tokio::runtime::Runtime::new().unwrap();
+/// # let _guard = tokio.enter();
+/// provide_context(QueryClient::with_options(QueryOptions {
+///     stale_time: Duration::from_secs(60),
+///     ..Default::default()
+/// }));
+///
+/// let fetches = Rc::new(Cell::new(0));
+/// let fetch_user = {
+///     let fetches = Rc::clone(&fetches);
+///     move |(_, id): (&str, u32)| {
+///         fetches.set(fetches.get() + 1);
+///         async move { format!("user {id}") }
+///     }
+/// };
+///
+/// // two parts of the app that need the same user share one fetch
+/// let a = use_query(|| ("users", 1), fetch_user.clone());
+/// let b = use_query(|| ("users", 1), fetch_user);
+/// assert_eq!(a.get(), Some("user 1".to_string()));
+/// assert_eq!(b.get(), a.get());
+/// assert_eq!(fetches.get(), 1);
+///
+/// // refetch everything about users
+/// expect_context::<QueryClient>().invalidate("users");
+/// assert_eq!(fetches.get(), 2);
+/// # runtime.dispose();
+/// ```
+#[derive(Clone)]
+pub struct QueryClient {
+    inner: Rc<QueryClientInner>,
+}
+
+struct QueryClientInner {
+    owner: Option<Owner>,
+    options: QueryOptions,
+    entries: RefCell<HashMap<(QueryKey, TypeId), Rc<dyn AnyQueryEntry>>>,
+}
+
+impl QueryClient {
+    /// Creates a client with the default [`QueryOptions`].
+    ///
+    /// The cached values live as long as the current owner.
+    pub fn new() -> Self {
+        Self::with_options(QueryOptions::default())
+    }
+
+    /// Creates a client with the given options.
+    ///
+    /// The cached values live as long as the current owner.
+    pub fn with_options(options: QueryOptions) -> Self {
+        Self {
+            inner: Rc::new(QueryClientInner {
+                owner: Owner::current(),
+                options,
+                entries: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the options of this client.
+    pub fn options(&self) -> QueryOptions {
+        self.inner.options
+    }
+
+    /// Marks every entry whose key starts with `prefix` as stale. The ones
+    /// that are in use are refetched right away, while the others are
+    /// refetched when a query starts using them again.
+    pub fn invalidate(&self, prefix: impl ToQueryKey) {
+        self.collect_garbage();
+        let prefix = prefix.to_query_key();
+        let entries = self
+            .inner
+            .entries
+            .borrow()
+            .iter()
+            .filter(|((key, _), _)| key.starts_with(&prefix))
+            .map(|(_, entry)| Rc::clone(entry))
+            .collect::<Vec<_>>();
+        batch(|| {
+            for entry in entries {
+                entry.invalidate();
+            }
+        });
+    }
+
+    /// Removes the entries that no query has used for at least
+    /// [`gc_time`](QueryOptions::gc_time).
+    pub fn collect_garbage(&self) {
+        let now = clock_now();
+        let gc_time = self.inner.options.gc_time;
+        let mut removed = Vec::new();
+        self.inner.entries.borrow_mut().retain(|_, entry| {
+            let expired = entry
+                .unused_since()
+                .is_some_and(|since| now.duration_since(since) >= gc_time);
+            if expired {
+                removed.push(Rc::clone(entry));
+            }
+            !expired
+        });
+        for entry in removed {
+            entry.dispose();
+        }
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.inner.entries.borrow().len()
+    }
+
+    /// Returns `true` if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entry<K, T>(&self, key: K, fetcher: Fetcher<K, T>) -> Rc<QueryEntry<K, T>>
+    where
+        K: ToQueryKey + Clone + 'static,
+        T: 'static,
+    {
+        let cache_key = (key.to_query_key(), TypeId::of::<QueryEntry<K, T>>());
+        let existing = self.inner.entries.borrow().get(&cache_key).cloned();
+        match existing {
+            Some(entry) => {
+                let entry = entry
+                    .as_any()
+                    .downcast::<QueryEntry<K, T>>()
+                    .unwrap_or_else(|_| unreachable!("entries are keyed by their type"));
+                // the most recent query decides how the entry is fetched
+                *entry.fetcher.borrow_mut() = fetcher;
+                entry
+            }
+            None => {
+                let create = || QueryEntry {
+                    client: Rc::downgrade(&self.inner),
+                    key,
+                    fetcher: RefCell::new(fetcher),
+                    options: self.inner.options,
+                    value: create_rw_signal(None),
+                    loading: create_rw_signal(false),
+                    updated_at: Cell::new(None),
+                    invalidated: Cell::new(false),
+                    version: Cell::new(0),
+                    subscribers: Cell::new(0),
+                    unused_since: Cell::new(Some(clock_now())),
+                    gc_timer: Cell::new(None),
+                    suspense: Default::default(),
+                };
+                let entry = Rc::new(match self.inner.owner {
+                    Some(owner) => with_owner(owner, create),
+                    None => create(),
+                });
+                self.inner
+                    .entries
+                    .borrow_mut()
+                    .insert(cache_key, Rc::clone(&entry) as Rc<dyn AnyQueryEntry>);
+                entry
+            }
+        }
+    }
+}
+
+impl Default for QueryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for QueryClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryClient")
+            .field("options", &self.inner.options)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+type Fetcher<K, T> = Rc<dyn Fn(K) -> Pin<Box<dyn Future<Output = T>>>>;
+
+struct QueryEntry<K: 'static, T: 'static> {
+    client: Weak<QueryClientInner>,
+    key: K,
+    fetcher: RefCell<Fetcher<K, T>>,
+    options: QueryOptions,
+    value: RwSignal<Option<T>>,
+    loading: RwSignal<bool>,
+    updated_at: Cell<Option<Instant>>,
+    invalidated: Cell<bool>,
+    version: Cell<usize>,
+    subscribers: Cell<usize>,
+    unused_since: Cell<Option<Instant>>,
+    /// Removes the entry once it has been unused for `gc_time`.
+    gc_timer: Cell<Option<TimerHandle>>,
+    suspense: Rc<Suspended>,
+}
+
+/// The parts of a [`QueryEntry`] the [`QueryClient`] needs without knowing
+/// its types.
+trait AnyQueryEntry {
+    fn as_any(self: Rc<Self>) -> Rc<dyn Any>;
+    fn invalidate(self: Rc<Self>);
+    fn unused_since(&self) -> Option<Instant>;
+    fn dispose(&self);
+}
+
+impl<K, T> QueryEntry<K, T>
+where
+    K: Clone + 'static,
+    T: 'static,
+{
+    fn is_stale(&self) -> bool {
+        self.invalidated.get()
+            || self.updated_at.get().is_none_or(|at| {
+                clock_now().saturating_duration_since(at) >= self.options.stale_time
+            })
+    }
+
+    fn subscribe(self: &Rc<Self>) {
+        self.subscribers.set(self.subscribers.get() + 1);
+        self.unused_since.set(None);
+        if let Some(timer) = self.gc_timer.take() {
+            timer.cancel();
+        }
+        // a fetch that is already running will do
+        if self.is_stale() && !self.loading.get_untracked() {
+            self.fetch();
+        }
+    }
+
+    fn unsubscribe(&self) {
+        let subscribers = self.subscribers.get().saturating_sub(1);
+        self.subscribers.set(subscribers);
+        if subscribers == 0 {
+            self.unused_since.set(Some(clock_now()));
+            let client = Weak::clone(&self.client);
+            self.gc_timer.set(set_timer(self.options.gc_time, move || {
+                if let Some(inner) = client.upgrade() {
+                    QueryClient { inner }.collect_garbage();
+                }
+            }));
+        }
+    }
+
+    /// Starts fetching the value, dropping the result of any fetch that is
+    /// already running.
+    fn fetch(self: &Rc<Self>) {
+        let version = self.version.get() + 1;
+        self.version.set(version);
+        self.loading.set(true);
+        self.suspense.wait();
+
+        let fut = (self.fetcher.borrow())(self.key.clone());
+        let entry = Rc::clone(self);
+        spawn_local(async move {
+            let value = fut.await;
+            if entry.version.get() == version {
+                entry.updated_at.set(Some(clock_now()));
+                entry.invalidated.set(false);
+                batch(|| {
+                    entry.suspense.ready();
+                    entry.value.try_set(Some(value));
+                    entry.loading.try_set(false);
+                });
+            }
+        });
+    }
+}
+
+impl<K, T> AnyQueryEntry for QueryEntry<K, T>
+where
+    K: Clone + 'static,
+    T: 'static,
+{
+    fn as_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+
+    fn invalidate(self: Rc<Self>) {
+        self.invalidated.set(true);
+        if self.subscribers.get() > 0 {
+            self.fetch();
+        }
+    }
+
+    fn unused_since(&self) -> Option<Instant> {
+        self.unused_since.get()
+    }
+
+    fn dispose(&self) {
+        // stops any running fetch from writing to the disposed signals
+        self.version.set(self.version.get() + 1);
+        if let Some(timer) = self.gc_timer.take() {
+            timer.cancel();
+        }
+        self.suspense.ready();
+        self.value.dispose();
+        self.loading.dispose();
+    }
+}
+
+/// A shared reference to a [`QueryEntry`], compared by identity so that it
+/// can be held in a [`Memo`].
+struct QueryEntryRef<K: 'static, T: 'static>(Rc<QueryEntry<K, T>>);
+
+impl<K, T> Clone for QueryEntryRef<K, T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<K, T> PartialEq for QueryEntryRef<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Fetches a value through the [`QueryClient`] provided in the current
+/// context, caching it under the key returned by `key`.
+///
+/// Works like [`create_local_resource`](crate::create_local_resource), with
+/// `key` as its source: whenever the key changes, the query switches to the
+/// entry for the new key. Unlike a resource, queries with the same key share
+/// one entry, and so one fetch. See [`QueryClient`] for how entries are
+/// cached and refetched.
+///
+/// # Panics
+/// Panics if no [`QueryClient`] has been provided.
+#[track_caller]
+pub fn use_query<K, T, Fu>(
+    key: impl Fn() -> K + 'static,
+    fetcher: impl Fn(K) -> Fu + 'static,
+) -> Query<K, T>
+where
+    K: ToQueryKey + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let client = expect_context::<QueryClient>();
+    client.collect_garbage();
+
+    let fetcher: Fetcher<K, T> = Rc::new(move |key| Box::pin(fetcher(key)));
+    let current = create_memo(move |_| QueryEntryRef(client.entry(key(), Rc::clone(&fetcher))));
+
+    // keeps the entry in use for as long as this query uses it
+    create_isomorphic_effect(move |prev: Option<QueryEntryRef<K, T>>| {
+        let entry = current.get();
+        if prev.as_ref() != Some(&entry) {
+            entry.0.subscribe();
+            if let Some(prev) = prev {
+                prev.0.unsubscribe();
+            }
+        }
+        entry
+    });
+    on_cleanup(move || {
+        if let Some(entry) = current.try_get_untracked() {
+            entry.0.unsubscribe();
+        }
+    });
+
+    Query { current }
+}
+
+/// A cached async value, created with [`use_query`].
+///
+/// Its value is `None` until the entry for its key has been fetched for the
+/// first time. After that, it keeps the cached value while refetching.
+///
+/// Reading the query under a [`SuspenseContext`](crate::SuspenseContext)
+/// keeps the context pending while the entry is being fetched.
+pub struct Query<K, T>
+where
+    K: 'static,
+    T: 'static,
+{
+    current: Memo<QueryEntryRef<K, T>>,
+}
+
+impl<K, T> Query<K, T>
+where
+    K: Clone + 'static,
+    T: 'static,
+{
+    /// Returns a signal that is `true` while the entry is being fetched.
+    #[track_caller]
+    pub fn loading(&self) -> Signal<bool> {
+        let current = self.current;
+        Signal::derive(move || current.with(|entry| entry.0.loading.get()))
+    }
+
+    /// Returns `true` if the value of the entry is stale, and will be
+    /// refetched the next time a query starts using it. This is not reactive.
+    #[track_caller]
+    pub fn is_stale(&self) -> bool {
+        self.current.with_untracked(|entry| entry.0.is_stale())
+    }
+
+    /// Refetches the entry, keeping the cached value until it resolves.
+    #[track_caller]
+    pub fn refetch(&self) {
+        self.current.with_untracked(|entry| entry.0.fetch());
+    }
+
+    fn try_with_inner<O>(&self, track: bool, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        let entry = if track {
+            self.current.try_get()?
+        } else {
+            self.current.try_get_untracked()?
+        };
+        if track {
+            entry.0.suspense.track();
+            entry.0.value.try_with(f)
+        } else {
+            entry.0.value.try_with_untracked(f)
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn panic_disposed() -> ! {
+    panic!("Tried to access a query that has been disposed.")
+}
+
+impl<K, T> Clone for Query<K, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, T> Copy for Query<K, T> {}
+
+impl<K, T> PartialEq for Query<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.current == other.current
+    }
+}
+
+impl<K, T> Eq for Query<K, T> {}
+
+impl<K, T> fmt::Debug for Query<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Query")
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+impl<K: Clone, T> SignalWith for Query<K, T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match self.try_with_inner(true, f) {
+            Some(value) => value,
+            None => panic_disposed(),
+        }
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.try_with_inner(true, f)
+    }
+}
+
+impl<K: Clone, T> SignalWithUntracked for Query<K, T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match self.try_with_inner(false, f) {
+            Some(value) => value,
+            None => panic_disposed(),
+        }
+    }
+
+    #[track_caller]
+    fn try_with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.try_with_inner(false, f)
+    }
+}
+
+impl<K: Clone, T: Clone> SignalGet for Query<K, T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get(&self) -> Option<T> {
+        self.with(Option::clone)
+    }
+
+    fn try_get(&self) -> Option<Option<T>> {
+        self.try_with(Option::clone)
+    }
+}
+
+impl<K: Clone, T: Clone> SignalGetUntracked for Query<K, T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get_untracked(&self) -> Option<T> {
+        self.with_untracked(Option::clone)
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<T>> {
+        self.try_with_untracked(Option::clone)
+    }
+}
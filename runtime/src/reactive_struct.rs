@@ -0,0 +1,58 @@
+use crate::StoreField;
+
+/// A struct that can be turned into a signal per field, or be read from a
+/// [`Store`](crate::Store) one field at a time.
+///
+/// This is meant to be implemented with `#[derive(Reactive)]`, which
+/// generates both companion types. For a struct `Settings`, these are
+/// `SettingsSignals` (an [`RwSignal`](crate::RwSignal) for each field, with
+/// `snapshot()` and `set_all()` to convert between the two) and
+/// `SettingsFields<T>` (a [`StoreField`] for each field).
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// #[derive(Clone, Debug, PartialEq, Reactive)]
+/// struct Settings {
+///     volume: u8,
+///     theme: String,
+/// }
+///
+/// let settings = Settings {
+///     volume: 7,
+///     theme: "dark".into(),
+/// }
+/// .into_signals();
+///
+/// settings.volume.set(11);
+/// assert_eq!(settings.snapshot().volume, 11);
+///
+/// settings.set_all(Settings {
+///     volume: 0,
+///     theme: "light".into(),
+/// });
+/// assert_eq!(settings.theme.get(), "light");
+///
+/// let store = create_store(settings.snapshot());
+/// let fields = Settings::fields(store);
+/// fields.volume.set(3);
+/// assert_eq!(store.with(|settings| settings.volume), 3);
+/// # runtime.dispose();
+/// ```
+///
+/// The snapshot is a plain value, so making it `Serialize` is enough to save
+/// or send the whole struct.
+pub trait Reactive: Sized + 'static {
+    /// A struct with an [`RwSignal`](crate::RwSignal) for each field.
+    type Signals: Copy + 'static;
+
+    /// A struct with a [`StoreField`] for each field, for a store holding a
+    /// `T`.
+    type Fields<T: 'static>: Copy;
+
+    /// Creates a signal for each field.
+    fn into_signals(self) -> Self::Signals;
+
+    /// Creates a path to each of the fields of the value at `field`.
+    fn fields<T: 'static>(field: StoreField<T, Self>) -> Self::Fields<T>;
+}
@@ -3,12 +3,18 @@ use crate::SharedContext;
 #[cfg(debug_assertions)]
 use crate::SpecialNonReactiveZone;
 use crate::{
-    create_isomorphic_effect, create_memo, create_render_effect, create_signal, queue_microtask,
-    runtime::with_runtime, serialization::Serializable, signal_prelude::format_signal_warning,
-    spawn::spawn_local, suspense::LocalStatus, use_context, GlobalSuspenseContext, Memo,
-    ReadSignal, ScopeProperty, Signal, SignalDispose, SignalGet, SignalGetUntracked, SignalSet,
-    SignalUpdate, SignalWith, SuspenseContext, WriteSignal,
+    batch, create_isomorphic_effect, create_memo, create_signal, on_cleanup, queue_microtask,
+    runtime::with_runtime,
+    serialization::Serializable,
+    signal_prelude::format_signal_warning,
+    spawn::spawn_local,
+    suspense::LocalStatus,
+    timer::{set_timer, TimerHandle},
+    use_context, GlobalSuspenseContext, Memo, ReadSignal, ScopeProperty, Signal, SignalDispose,
+    SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, SuspenseContext,
+    WriteSignal,
 };
+use futures::future::{abortable, AbortHandle};
 use std::{
     any::Any,
     cell::{Cell, RefCell},
@@ -19,6 +25,7 @@ use std::{
     panic::Location,
     pin::Pin,
     rc::Rc,
+    time::Duration,
 };
 
 /// Creates a [`Resource`](crate::Resource), which is a signal that reflects the
@@ -92,6 +99,7 @@ use std::{
         )
     )
 )]
+#[track_caller]
 pub fn create_resource<S, T, Fu>(
     source: impl Fn() -> S + 'static,
     fetcher: impl Fn(S) -> Fu + 'static,
@@ -142,6 +150,7 @@ where
         fetcher,
         initial_value,
         ResourceSerialization::Serializable,
+        ResourceOptions::default(),
     )
 }
 
@@ -185,24 +194,98 @@ where
     T: Serializable + 'static,
     Fu: Future<Output = T> + 'static,
 {
-    create_resource_helper(source, fetcher, None, ResourceSerialization::Blocking)
+    create_resource_helper(
+        source,
+        fetcher,
+        None,
+        ResourceSerialization::Blocking,
+        ResourceOptions::default(),
+    )
+}
+
+/// Creates a [`Resource`](crate::Resource) that polls, retries, or times out
+/// according to the given [`ResourceOptions`].
+///
+/// Otherwise, this is the same as [`create_resource()`].
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::Cell, rc::Rc, time::Duration};
+/// # let runtime = create_runtime();
+/// # let tokio = tokio::runtime::Runtime::new().unwrap();
+/// # let _guard = tokio.enter();
+/// let clock = TestClock::install();
+/// let attempts = Rc::new(Cell::new(0));
+/// let flaky = {
+///     let attempts = Rc::clone(&attempts);
+///     move |_| {
+///         attempts.set(attempts.get() + 1);
+///         let attempt = attempts.get();
+///         async move {
+///             if attempt < 3 {
+///                 Err(format!("attempt {attempt} failed"))
+///             } else {
+///                 Ok(attempt)
+///             }
+///         }
+///     }
+/// };
+///
+/// let data = create_resource_with_options(
+///     || (),
+///     flaky,
+///     ResourceOptions::default().retry(5, Duration::from_millis(1)),
+/// );
+/// assert!(data.loading().get());
+///
+/// // the retries wait 1ms and then 2ms
+/// clock.advance(Duration::from_millis(3));
+/// assert_eq!(data.get(), Some(Ok(3)));
+/// assert_eq!(data.attempt().get(), 3);
+/// assert_eq!(data.error().get(), None);
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_resource_with_options<S, T, Fu>(
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_resource_helper(
+        source,
+        fetcher,
+        None,
+        ResourceSerialization::Serializable,
+        options,
+    )
 }
 
+#[track_caller]
 fn create_resource_helper<S, T, Fu>(
     source: impl Fn() -> S + 'static,
     fetcher: impl Fn(S) -> Fu + 'static,
     initial_value: Option<T>,
     serializable: ResourceSerialization,
+    options: ResourceOptions<T>,
 ) -> Resource<S, T>
 where
     S: PartialEq + Clone + 'static,
     T: Serializable + 'static,
     Fu: Future<Output = T> + 'static,
 {
+    #[cfg(any(debug_assertions, feature = "ssr"))]
+    let defined_at = std::panic::Location::caller();
     let resolved = initial_value.is_some();
     let (value, set_value) = create_signal(initial_value);
 
     let (loading, set_loading) = create_signal(false);
+    let (attempt, set_attempt) = create_signal(0);
+    let (error, set_error) = create_signal(None);
 
     //crate::macros::debug_warn!("creating fetcher");
     let fetcher = Rc::new(move |s| Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>);
@@ -213,8 +296,15 @@ where
         set_value,
         loading,
         set_loading,
+        attempt,
+        set_attempt,
+        error,
+        set_error,
         source,
         fetcher,
+        options: Rc::new(options),
+        in_flight: Default::default(),
+        timer: Default::default(),
         resolved: Rc::new(Cell::new(resolved)),
         scheduled: Rc::new(Cell::new(false)),
         version: Rc::new(Cell::new(0)),
@@ -227,10 +317,21 @@ where
     let id = with_runtime(|runtime| {
         let r = Rc::clone(&r) as Rc<dyn SerializableResource>;
         let id = runtime.create_serializable_resource(r);
-        runtime.push_scope_property(ScopeProperty::Resource(id));
+        runtime.register_property(
+            ScopeProperty::Resource(id),
+            #[cfg(debug_assertions)]
+            defined_at,
+        );
         id
     })
     .expect("tried to create a Resource in a Runtime that has been disposed.");
+    on_cleanup({
+        let r = Rc::clone(&r);
+        move || {
+            r.cancel();
+            _ = with_runtime(|runtime| runtime.shared_context.borrow_mut().resolvers.remove(&id));
+        }
+    });
 
     create_isomorphic_effect({
         let r = Rc::clone(&r);
@@ -244,7 +345,7 @@ where
         source_ty: PhantomData,
         out_ty: PhantomData,
         #[cfg(any(debug_assertions, feature = "ssr"))]
-        defined_at: std::panic::Location::caller(),
+        defined_at,
     }
 }
 
@@ -294,6 +395,7 @@ where
         )
     )
 )]
+#[track_caller]
 pub fn create_local_resource<S, T, Fu>(
     source: impl Fn() -> S + 'static,
     fetcher: impl Fn(S) -> Fu + 'static,
@@ -327,6 +429,7 @@ where
         )
     )
 )]
+#[track_caller]
 pub fn create_local_resource_with_initial_value<S, T, Fu>(
     source: impl Fn() -> S + 'static,
     fetcher: impl Fn(S) -> Fu + 'static,
@@ -337,10 +440,47 @@ where
     T: 'static,
     Fu: Future<Output = T> + 'static,
 {
+    create_local_resource_helper(source, fetcher, initial_value, ResourceOptions::default())
+}
+
+/// Creates a _local_ [`Resource`](crate::Resource) that polls, retries, or
+/// times out according to the given [`ResourceOptions`].
+///
+/// Otherwise, this is the same as [`create_local_resource()`].
+#[track_caller]
+pub fn create_local_resource_with_options<S, T, Fu>(
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    create_local_resource_helper(source, fetcher, None, options)
+}
+
+#[track_caller]
+fn create_local_resource_helper<S, T, Fu>(
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    initial_value: Option<T>,
+    options: ResourceOptions<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    #[cfg(any(debug_assertions, feature = "ssr"))]
+    let defined_at = std::panic::Location::caller();
     let resolved = initial_value.is_some();
     let (value, set_value) = create_signal(initial_value);
 
     let (loading, set_loading) = create_signal(false);
+    let (attempt, set_attempt) = create_signal(0);
+    let (error, set_error) = create_signal(None);
 
     let fetcher = Rc::new(move |s| Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>);
     let source = create_memo(move |_| source());
@@ -350,8 +490,15 @@ where
         set_value,
         loading,
         set_loading,
+        attempt,
+        set_attempt,
+        error,
+        set_error,
         source,
         fetcher,
+        options: Rc::new(options),
+        in_flight: Default::default(),
+        timer: Default::default(),
         resolved: Rc::new(Cell::new(resolved)),
         scheduled: Rc::new(Cell::new(false)),
         version: Rc::new(Cell::new(0)),
@@ -364,14 +511,23 @@ where
     let id = with_runtime(|runtime| {
         let r = Rc::clone(&r) as Rc<dyn UnserializableResource>;
         let id = runtime.create_unserializable_resource(r);
-        runtime.push_scope_property(ScopeProperty::Resource(id));
+        runtime.register_property(
+            ScopeProperty::Resource(id),
+            #[cfg(debug_assertions)]
+            defined_at,
+        );
         id
     })
     .expect("tried to create a Resource in a runtime that has been disposed.");
+    on_cleanup({
+        let r = Rc::clone(&r);
+        move || r.cancel()
+    });
 
     // This is a local resource, so we're always going to handle it on the
-    // client
-    create_render_effect({
+    // client. It loads in the default phase, rather than waiting for
+    // rendering if that is deferred.
+    create_isomorphic_effect({
         let r = Rc::clone(&r);
         move |_| r.load(false)
     });
@@ -381,96 +537,48 @@ where
         source_ty: PhantomData,
         out_ty: PhantomData,
         #[cfg(any(debug_assertions, feature = "ssr"))]
-        defined_at: std::panic::Location::caller(),
+        defined_at,
     }
 }
 
-#[cfg(not(feature = "hydrate"))]
-fn load_resource<S, T>(id: ResourceId, r: Rc<ResourceState<S, T>>)
-where
-    S: PartialEq + Clone + 'static,
-    T: 'static,
-{
-    _ = id;
-    SUPPRESS_RESOURCE_LOAD.with(|s| {
-        if !s.get() {
-            r.load(false)
-        }
-    });
-}
-
-#[cfg(feature = "hydrate")]
 fn load_resource<S, T>(id: ResourceId, r: Rc<ResourceState<S, T>>)
 where
     S: PartialEq + Clone + 'static,
     T: Serializable + 'static,
 {
-    use wasm_bindgen::{JsCast, UnwrapThrowExt};
-
-    _ = with_runtime(|runtime| {
+    // a resource resolved by another process only takes the values it sends
+    let from_other_process = with_runtime(|runtime| {
         let mut context = runtime.shared_context.borrow_mut();
-        if let Some(data) = context.resolved_resources.remove(&id) {
-            // The server already sent us the serialized resource value, so
-            // deserialize & set it now
-            context.pending_resources.remove(&id); // no longer pending
-            r.resolved.set(true);
-
-            let res = T::de(&data).unwrap_or_else(|e| {
-                panic!(
-                    "could not deserialize Resource<{}> JSON for {id:?}: {e:?}",
-                    std::any::type_name::<T>()
-                )
-            });
-
-            r.set_value.update(|n| *n = Some(res));
-            r.set_loading.update(|n| *n = false);
+        if !context.server_resources.contains(&id) {
+            return None;
+        }
+        context.resolvers.entry(id).or_insert_with(|| {
+            let r = Rc::clone(&r);
+            Rc::new(move |value: String| r.resolve_serialized(id, &value))
+        });
+        Some(context.resolved_resources.remove(&id))
+    })
+    .ok()
+    .flatten();
 
+    match from_other_process {
+        Some(resolved) => {
             // for reactivity
             r.source.track();
-        } else if context.pending_resources.remove(&id) {
-            // We're still waiting for the resource, add a "resolver" closure so
-            // that it will be set as soon as the server sends the serialized
-            // value
-            r.set_loading.update(|n| *n = true);
-
-            let resolve = {
-                let resolved = r.resolved.clone();
-                let set_value = r.set_value;
-                let set_loading = r.set_loading;
-                move |res: String| {
-                    let res = T::de(&res).unwrap_or_else(|e| {
-                        panic!(
-                            "could not deserialize Resource JSON for {id:?}: \
-                             {e:?}"
-                        )
-                    });
-                    resolved.set(true);
-                    set_value.update(|n| *n = Some(res));
-                    set_loading.update(|n| *n = false);
+            match resolved {
+                Some(value) => r.resolve_serialized(id, &value),
+                None if !r.resolved.get() => {
+                    r.set_loading.try_set(true);
                 }
-            };
-            let resolve =
-                wasm_bindgen::closure::Closure::wrap(Box::new(resolve) as Box<dyn Fn(String)>);
-            let resource_resolvers = js_sys::Reflect::get(
-                &web_sys::window().unwrap(),
-                &wasm_bindgen::JsValue::from_str("__LEPTOS_RESOURCE_RESOLVERS"),
-            )
-            .expect_throw("no __LEPTOS_RESOURCE_RESOLVERS found in the JS global scope");
-            let id = serde_json::to_string(&id).expect_throw("could not serialize Resource ID");
-            _ = js_sys::Reflect::set(
-                &resource_resolvers,
-                &wasm_bindgen::JsValue::from_str(&id),
-                resolve.as_ref().unchecked_ref(),
-            );
-
-            // for reactivity
-            r.source.track()
-        } else {
-            // Server didn't mark the resource as pending, so load it on the
-            // client
-            r.load(false);
+                None => {}
+            }
         }
-    })
+        None => SUPPRESS_RESOURCE_LOAD.with(|s| {
+            if !s.get() {
+                r.load(false)
+            }
+        }),
+    }
 }
 
 impl<S, T> Resource<S, T>
@@ -568,6 +676,33 @@ where
         }
     }
 
+    /// Returns a signal with the number of the current attempt to load the
+    /// resource, or of the last one if it isn't loading. This only goes
+    /// above `1` for resources that [retry](ResourceOptions::retry).
+    pub fn attempt(&self) -> Signal<usize> {
+        with_runtime(|runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.attempt)
+        })
+        .expect(
+            "tried to call Resource::attempt() in a runtime that has already \
+             been disposed.",
+        )
+        .into()
+    }
+
+    /// Returns a signal with the reason the last load of the resource
+    /// failed, or `None` if it succeeded or is still running.
+    pub fn error(&self) -> Signal<Option<ResourceError>> {
+        with_runtime(|runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.error)
+        })
+        .expect(
+            "tried to call Resource::error() in a runtime that has already \
+             been disposed.",
+        )
+        .into()
+    }
+
     /// Re-runs the async function with the current source data.
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
@@ -1091,7 +1226,6 @@ where
 {
 }
 
-#[derive(Clone)]
 pub(crate) struct ResourceState<S, T>
 where
     S: 'static,
@@ -1101,9 +1235,19 @@ where
     set_value: WriteSignal<Option<T>>,
     pub loading: ReadSignal<bool>,
     set_loading: WriteSignal<bool>,
+    pub attempt: ReadSignal<usize>,
+    set_attempt: WriteSignal<usize>,
+    pub error: ReadSignal<Option<ResourceError>>,
+    set_error: WriteSignal<Option<ResourceError>>,
     source: Memo<S>,
     #[allow(clippy::type_complexity)]
     fetcher: Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
+    options: Rc<ResourceOptions<T>>,
+    /// Aborts the future of the current load.
+    in_flight: Rc<RefCell<Option<AbortHandle>>>,
+    /// The timeout of the current attempt, the next retry, or the next
+    /// refetch scheduled by [`ResourceOptions::refetch_interval`].
+    timer: Rc<Cell<Option<TimerHandle>>>,
     resolved: Rc<Cell<bool>>,
     scheduled: Rc<Cell<bool>>,
     version: Rc<Cell<usize>>,
@@ -1113,6 +1257,140 @@ where
     should_send_to_client: Rc<Cell<Option<bool>>>,
 }
 
+// everything in the state is shared, so it can be cloned whatever `S` and
+// `T` are
+impl<S, T> Clone for ResourceState<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value,
+            set_value: self.set_value,
+            loading: self.loading,
+            set_loading: self.set_loading,
+            attempt: self.attempt,
+            set_attempt: self.set_attempt,
+            error: self.error,
+            set_error: self.set_error,
+            source: self.source,
+            fetcher: Rc::clone(&self.fetcher),
+            options: Rc::clone(&self.options),
+            in_flight: Rc::clone(&self.in_flight),
+            timer: Rc::clone(&self.timer),
+            resolved: Rc::clone(&self.resolved),
+            scheduled: Rc::clone(&self.scheduled),
+            version: Rc::clone(&self.version),
+            suspense_contexts: Rc::clone(&self.suspense_contexts),
+            serializable: self.serializable,
+            #[cfg(feature = "experimental-islands")]
+            should_send_to_client: Rc::clone(&self.should_send_to_client),
+        }
+    }
+}
+
+/// The longest a resource waits before retrying, however many retries the
+/// backoff of [`ResourceOptions::retry`] has doubled over.
+pub const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// How a [`Resource`] created with [`create_resource_with_options`] or
+/// [`create_local_resource_with_options`] polls, retries and times out.
+///
+/// By default, a resource does none of these.
+pub struct ResourceOptions<T> {
+    refetch_interval: Option<Duration>,
+    timeout: Option<Duration>,
+    retries: usize,
+    backoff: Duration,
+    is_err: Option<fn(&T) -> bool>,
+}
+
+impl<T> Default for ResourceOptions<T> {
+    fn default() -> Self {
+        Self {
+            refetch_interval: None,
+            timeout: None,
+            retries: 0,
+            backoff: Duration::ZERO,
+            is_err: None,
+        }
+    }
+}
+
+impl<T> Debug for ResourceOptions<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceOptions")
+            .field("refetch_interval", &self.refetch_interval)
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("backoff", &self.backoff)
+            .finish()
+    }
+}
+
+impl<T> ResourceOptions<T> {
+    /// Refetches the resource once `interval` has passed since it last
+    /// finished loading.
+    ///
+    /// The refetch runs on the runtime's timers, so it only happens if
+    /// whatever drives the runtime calls
+    /// [`RuntimeId::run_timers`](crate::RuntimeId::run_timers).
+    pub fn refetch_interval(mut self, interval: Duration) -> Self {
+        self.refetch_interval = Some(interval);
+        self
+    }
+
+    /// Gives up on an attempt to load the resource once `timeout` has
+    /// passed. If there are no retries left, the resource keeps its
+    /// previous value, and its [`error`](Resource::error) is
+    /// [`ResourceError::TimedOut`].
+    ///
+    /// Like [`refetch_interval`](ResourceOptions::refetch_interval), this
+    /// runs on the runtime's timers. Outside the browser, the future of an
+    /// attempt blocks the thread, so it can only time out if it runs the
+    /// timers itself while it is waiting.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn failed(&self, output: &Option<T>) -> bool {
+        match output {
+            Some(output) => self.is_err.as_ref().is_some_and(|is_err| is_err(output)),
+            None => true,
+        }
+    }
+}
+
+impl<T, E> ResourceOptions<Result<T, E>> {
+    /// Tries again up to `retries` times when loading the resource returns
+    /// an `Err(_)` or times out, waiting `backoff` before the first retry
+    /// and twice as long before each of the next ones, up to
+    /// [`MAX_RETRY_BACKOFF`].
+    ///
+    /// If every attempt fails, the resource holds the last `Err(_)`, and its
+    /// [`error`](Resource::error) is [`ResourceError::Failed`].
+    ///
+    /// The retries wait on the runtime's timers, like
+    /// [`refetch_interval`](ResourceOptions::refetch_interval).
+    pub fn retry(mut self, retries: usize, backoff: Duration) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self.is_err = Some(Result::is_err);
+        self
+    }
+}
+
+/// Why the last load of a [`Resource`] failed, as returned by
+/// [`Resource::error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, thiserror::Error)]
+pub enum ResourceError {
+    /// Every attempt returned an `Err(_)`.
+    #[error("every attempt to load the resource failed")]
+    Failed,
+    /// The last attempt took longer than the
+    /// [`timeout`](ResourceOptions::timeout).
+    #[error("loading the resource timed out")]
+    TimedOut,
+}
+
 /// Whether and how the resource can be serialized.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum ResourceSerialization {
@@ -1291,6 +1569,10 @@ where
         if refetching && self.scheduled.get() {
             return;
         }
+        // a load that is replaced keeps holding the suspense contexts for
+        // the one replacing it
+        let was_loading = self.loading.get_untracked();
+        self.stop();
 
         // if it's 1) in normal mode and is read, or
         // 2) is in island mode and read in an island, tell it to ship
@@ -1299,13 +1581,9 @@ where
             self.should_send_to_client.set(Some(true));
         }
 
-        let version = self.version.get() + 1;
-        self.version.set(version);
         self.scheduled.set(false);
 
         _ = self.source.try_with(|source| {
-            let fut = (self.fetcher)(source.clone());
-
             // `scheduled` is true for the rest of this code only
             self.scheduled.set(true);
             queue_microtask({
@@ -1315,41 +1593,176 @@ where
                 }
             });
 
-            self.set_loading.update(|n| *n = true);
+            batch(|| {
+                self.set_loading.update(|n| *n = true);
+                self.set_error.set(None);
+            });
 
             // increment counter everywhere it's read
-            let suspense_contexts = self.suspense_contexts.clone();
+            if !was_loading {
+                for suspense_context in self.suspense_contexts.borrow().iter() {
+                    suspense_context.increment(self.serializable != ResourceSerialization::Local);
+                    if self.serializable == ResourceSerialization::Blocking {
+                        suspense_context.should_block.set_value(true);
+                    }
+                }
+            }
+
+            // the first attempt starts right away, like without retries
+            self.attempt(source.clone(), 1);
+        });
+    }
+
+    /// Runs one attempt at loading the resource, giving up on it once the
+    /// [`timeout`](ResourceOptions::timeout) has passed.
+    ///
+    /// The timeout and the wait before the next retry run on the runtime's
+    /// timers rather than as futures, since [`spawn_local`] blocks until its
+    /// future is done outside the browser.
+    fn attempt(&self, source: S, attempt: usize) {
+        // every attempt has its own version, so that one that has been given
+        // up on, or replaced, can't set the value any more
+        let version = self.version.get() + 1;
+        self.version.set(version);
+        self.set_attempt.try_set(attempt);
+        let (fut, handle) = abortable((self.fetcher)(source.clone()));
+        *self.in_flight.borrow_mut() = Some(handle);
+
+        // set before running the future, which may finish right away
+        if let Some(timeout) = self.options.timeout {
+            let this = self.clone();
+            let source = source.clone();
+            let handle = set_timer(timeout, move || {
+                if let Some(handle) = this.in_flight.take() {
+                    handle.abort();
+                }
+                this.attempted(source, version, attempt, None);
+            });
+            self.timer.set(handle);
+        }
 
-            for suspense_context in suspense_contexts.borrow().iter() {
-                suspense_context.increment(self.serializable != ResourceSerialization::Local);
-                if self.serializable == ResourceSerialization::Blocking {
-                    suspense_context.should_block.set_value(true);
+        spawn_local({
+            let this = self.clone();
+            async move {
+                if let Ok(res) = fut.await {
+                    this.attempted(source, version, attempt, Some(res));
                 }
             }
+        });
+    }
 
-            // run the Future
-            let serializable = self.serializable;
-            spawn_local({
-                let resolved = self.resolved.clone();
-                let set_value = self.set_value;
-                let set_loading = self.set_loading;
-                let last_version = self.version.clone();
-                async move {
-                    let res = fut.await;
-
-                    if version == last_version.get() {
-                        resolved.set(true);
-                        set_value.try_update(|n| *n = Some(res));
-                        set_loading.try_update(|n| *n = false);
-                    }
+    /// Retries a failed attempt if there are retries left, and finishes the
+    /// load otherwise. `res` is `None` if the attempt timed out.
+    fn attempted(&self, source: S, version: usize, attempt: usize, res: Option<T>) {
+        if version != self.version.get() {
+            return;
+        }
+        let version = version + 1;
+        self.version.set(version);
+        self.in_flight.take();
+        if let Some(handle) = self.timer.take() {
+            handle.cancel();
+        }
 
-                    for suspense_context in suspense_contexts.borrow().iter() {
-                        suspense_context.decrement(serializable != ResourceSerialization::Local);
-                    }
+        let failed = self.options.failed(&res);
+        if failed && attempt <= self.options.retries {
+            let backoff = u32::try_from(attempt - 1)
+                .ok()
+                .and_then(|doublings| 2u32.checked_pow(doublings))
+                .and_then(|factor| self.options.backoff.checked_mul(factor))
+                .map_or(MAX_RETRY_BACKOFF, |backoff| backoff.min(MAX_RETRY_BACKOFF));
+            let this = self.clone();
+            let handle = set_timer(backoff, move || {
+                // unless the value has been set in the meantime
+                if this.version.get() == version {
+                    this.attempt(source, attempt + 1);
                 }
-            })
+            });
+            self.timer.set(handle);
+            return;
+        }
+
+        let error = failed.then_some(match res {
+            Some(_) => ResourceError::Failed,
+            None => ResourceError::TimedOut,
         });
+        batch(|| {
+            if let Some(res) = res {
+                self.resolved.set(true);
+                self.set_value.try_update(|n| *n = Some(res));
+            }
+            self.set_error.try_set(error);
+            self.set_loading.try_update(|n| *n = false);
+        });
+        self.release_suspense();
+        self.schedule_poll();
+    }
+
+    /// Tells the suspense contexts the resource was read under that it is
+    /// done loading.
+    fn release_suspense(&self) {
+        let suspense_contexts = self.suspense_contexts.borrow().clone();
+        for suspense_context in suspense_contexts {
+            suspense_context.decrement(self.serializable != ResourceSerialization::Local);
+        }
+    }
+
+    /// Sets a timer for the next refetch, if the resource polls.
+    fn schedule_poll(&self) {
+        if let Some(interval) = self.options.refetch_interval {
+            let this = self.clone();
+            let handle = set_timer(interval, move || this.load(true));
+            self.timer.set(handle);
+        }
+    }
+
+    /// Stops the current load and the next poll, if there are any.
+    pub(crate) fn cancel(&self) {
+        // the signals may already be gone if this runs as the owner is
+        // cleaned up
+        if self.loading.try_get_untracked().unwrap_or(false) {
+            self.release_suspense();
+        }
+        self.stop();
+    }
+
+    /// Aborts the current attempt, and cancels its timer.
+    fn stop(&self) {
+        if let Some(handle) = self.in_flight.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.timer.take() {
+            handle.cancel();
+        }
+    }
+
+    /// Sets the value from one serialized by another process.
+    fn resolve_serialized(&self, id: ResourceId, value: &str)
+    where
+        T: Serializable,
+    {
+        match T::de(value) {
+            Ok(value) => {
+                let first = !self.resolved.replace(true);
+                batch(|| {
+                    self.set_value.try_set(Some(value));
+                    self.set_loading.try_set(false);
+                    // suspense contexts that read it while it had no value are
+                    // waiting for it
+                    if first {
+                        for suspense_context in self.suspense_contexts.borrow().iter() {
+                            suspense_context
+                                .decrement(self.serializable != ResourceSerialization::Local);
+                        }
+                    }
+                });
+            }
+            Err(e) => {
+                crate::macros::debug_warn!("could not deserialize the value of {id:?}: {e}");
+            }
+        }
     }
+
     #[cfg_attr(
         any(debug_assertions, feature = "ssr"),
         instrument(level = "trace", skip_all,)
@@ -1402,6 +1815,10 @@ pub(crate) trait SerializableResource {
     ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>>;
 
     fn should_send_to_client(&self) -> bool;
+
+    /// Calls `send` with the serialized value every time it changes, until
+    /// the current owner is cleaned up.
+    fn watch_serialized(&self, send: Box<dyn Fn(String)>);
 }
 
 impl<S, T> SerializableResource for ResourceState<S, T>
@@ -1441,6 +1858,20 @@ where
             true
         }
     }
+
+    fn watch_serialized(&self, send: Box<dyn Fn(String)>) {
+        let value = self.value;
+        create_isomorphic_effect(move |_| {
+            let serialized = value.with(|value| value.as_ref().map(T::ser));
+            match serialized {
+                Some(Ok(serialized)) => send(serialized),
+                Some(Err(e)) => {
+                    crate::macros::debug_warn!("could not serialize a resource: {e}")
+                }
+                None => {}
+            }
+        });
+    }
 }
 
 pub(crate) trait UnserializableResource {
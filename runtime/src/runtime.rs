@@ -3,15 +3,18 @@ use crate::SpecialNonReactiveZone;
 use crate::{
     hydration::SharedContext,
     node::{Disposer, NodeId, ReactiveNode, ReactiveNodeState, ReactiveNodeType},
-    AnyComputation, AnyResource, EffectState, Memo, MemoState, ReadSignal, ResourceId,
-    ResourceState, RwSignal, SerializableResource, StoredValueId, Trigger, UnserializableResource,
-    WriteSignal,
+    send_signal::SyncWrites,
+    snapshot::Registry,
+    timer::Timers,
+    AnyComputation, AnyResource, EffectOptions, EffectPhase, EffectState, Memo, MemoState,
+    ReadSignal, ResourceId, ResourceState, RwSignal, SerializableResource, StoredValueId, Trigger,
+    UnserializableResource, ValueKind, WriteSignal,
 };
 use core::hash::BuildHasherDefault;
 use futures::stream::FuturesUnordered;
 use indexmap::IndexSet;
 use pin_project::pin_project;
-use rustc_hash::{FxHashMap, FxHasher};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 use std::{
     any::{Any, TypeId},
@@ -57,10 +60,51 @@ pub(crate) struct Runtime {
     #[allow(clippy::type_complexity)]
     pub contexts: RefCell<SparseSecondaryMap<NodeId, FxHashMap<TypeId, Box<dyn Any>>>>,
     pub pending_effects: RefCell<Vec<NodeId>>,
+    /// Owners whose subtrees have been [paused](Owner::pause).
+    pub paused_owners: RefCell<FxHashSet<NodeId>>,
+    /// Effects that became dirty while they were paused, in the order they
+    /// did, to be run when they are resumed.
+    pub paused_effects: RefCell<FxIndexSet<NodeId>>,
+    /// The phase and priority of effects that don't use the defaults.
+    pub effect_options: RefCell<SparseSecondaryMap<NodeId, EffectOptions>>,
+    /// Phases whose effects only run when asked to with
+    /// [`RuntimeId::run_phase`].
+    pub deferred_phases: RefCell<FxHashSet<EffectPhase>>,
+    /// The phase whose effects are running, if any. Effects that run pending
+    /// effects themselves, by writing to a signal, don't get to run those of
+    /// a later phase before this one has finished.
+    pub draining_phase: Cell<Option<EffectPhase>>,
+    /// Whether a memo has been created with
+    /// [`create_lazy_memo`](crate::create_lazy_memo), so that there may be
+    /// memos to release whenever a node stops depending on its sources.
+    pub has_lazy_memos: Cell<bool>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
     pub batching: Cell<bool>,
+    /// Memos and effects that are currently being updated, outermost first.
+    pub updating: RefCell<FxIndexSet<NodeId>>,
+    /// Effects that were marked dirty while they were still running, along
+    /// with the chain of nodes that led back to them.
+    pub deferred_effects: RefCell<Vec<(NodeId, Vec<NodeId>)>>,
+    pub flushing_deferred: Cell<bool>,
+    pub max_effect_reruns: Cell<usize>,
+    pub root: NodeId,
+    /// Children created by [`as_child_of_current_owner`] whose [`Disposer`]
+    /// has not been dropped yet.
+    pub child_nodes: RefCell<FxIndexSet<NodeId>>,
+    pub timers: RefCell<Timers>,
+    pub registry: RefCell<Registry>,
+    pub sync_writes: RefCell<SyncWrites>,
+    #[cfg(debug_assertions)]
+    pub stored_value_locations:
+        RefCell<SecondaryMap<StoredValueId, &'static std::panic::Location<'static>>>,
+    #[cfg(debug_assertions)]
+    pub resource_locations:
+        RefCell<SecondaryMap<ResourceId, &'static std::panic::Location<'static>>>,
 }
 
+/// The default for [`RuntimeId::set_max_effect_reruns`].
+pub const DEFAULT_MAX_EFFECT_RERUNS: usize = 100;
+
 /// The current reactive runtime.
 pub fn current_runtime() -> RuntimeId {
     Runtime::current()
@@ -94,6 +138,88 @@ impl Owner {
 
         Self(NodeId::from(KeyData::from_ffi(ffi)))
     }
+
+    /// Pauses every effect owned by this owner, or by any of its descendants.
+    ///
+    /// While paused, an effect whose dependencies change is only marked dirty
+    /// rather than run. It runs once, however many times it was marked, when
+    /// the subtree is [resumed](Owner::resume). This includes the effects
+    /// behind [`watch`](crate::watch), so a paused subtree stops doing work
+    /// altogether, such as for a tab that isn't visible.
+    ///
+    /// Effects still run once when they are created, even in a paused
+    /// subtree.
+    ///
+    /// ```
+    /// # use goober_runtime::*;
+    /// # let runtime = create_runtime();
+    /// let (count, set_count) = create_signal(0);
+    /// let (seen, set_seen) = create_signal(0);
+    /// let (tab, _disposer) = as_child_of_current_owner(|_| {
+    ///     create_isomorphic_effect(move |_| set_seen.set(count.get()));
+    ///     Owner::current().unwrap()
+    /// })(());
+    ///
+    /// tab.pause();
+    /// set_count.set(1);
+    /// set_count.set(2);
+    /// assert_eq!(seen.get_untracked(), 0);
+    ///
+    /// tab.resume();
+    /// assert_eq!(seen.get_untracked(), 2);
+    /// # runtime.dispose();
+    /// ```
+    pub fn pause(&self) {
+        _ = with_runtime(|runtime| runtime.paused_owners.borrow_mut().insert(self.0));
+    }
+
+    /// Resumes a subtree [paused](Owner::pause) with this owner, running each
+    /// of its effects that became dirty in the meantime, unless it is still
+    /// paused by another owner above it.
+    pub fn resume(&self) {
+        _ = with_runtime(|runtime| {
+            if !runtime.paused_owners.borrow_mut().remove(&self.0) {
+                return;
+            }
+            let parked = runtime.paused_effects.take();
+            runtime.pending_effects.borrow_mut().extend(parked);
+            // effects that are still paused are simply parked again
+            runtime.run_effects();
+        });
+    }
+
+    /// Whether this owner, or any owner above it, is
+    /// [paused](Owner::pause).
+    pub fn is_paused(&self) -> bool {
+        with_runtime(|runtime| runtime.is_paused(self.0)).unwrap_or(false)
+    }
+}
+
+/// Restores [`Runtime::draining_phase`] once pending effects have run, or
+/// panicked.
+struct RestoreDrainingPhase<'a>(&'a Runtime, Option<EffectPhase>);
+
+impl Drop for RestoreDrainingPhase<'_> {
+    fn drop(&mut self) {
+        self.0.draining_phase.set(self.1);
+    }
+}
+
+/// Pops the node that is being updated off [`Runtime::updating`] when
+/// dropped, so that a memo or effect that panics isn't mistaken for part of a
+/// cycle the next time it is updated.
+struct PopUpdating<'a>(&'a Runtime);
+
+impl Drop for PopUpdating<'_> {
+    fn drop(&mut self) {
+        let mut updating = self.0.updating.borrow_mut();
+        updating.pop();
+        if updating.is_empty() && std::thread::panicking() {
+            // the update was abandoned, so nothing is left to re-run
+            self.0.deferred_effects.borrow_mut().clear();
+            self.0.flushing_deferred.set(false);
+        }
+    }
 }
 
 // This core Runtime impl block handles all the work of marking and updating
@@ -108,6 +234,27 @@ impl Runtime {
     }
 
     pub(crate) fn update_if_necessary(&self, node_id: NodeId) {
+        // re-entering a node that is still being updated means the graph has
+        // a cycle in it, which would otherwise overflow the stack
+        let cycle = {
+            let updating = self.updating.borrow();
+            updating
+                .get_index_of(&node_id)
+                .map(|start| updating[start..].iter().copied().collect::<Vec<_>>())
+        };
+        if let Some(cycle) = cycle {
+            if self.is_effect(node_id) {
+                // an effect that (indirectly) dirties itself while running
+                // is run again once the current update has finished
+                self.deferred_effects.borrow_mut().push((node_id, cycle));
+                return;
+            }
+            self.report_error(ReactiveSystemError::Cycle(self.locations(&cycle)));
+        }
+
+        self.updating.borrow_mut().insert(node_id);
+        let updating = PopUpdating(self);
+
         if self.current_state(node_id) == ReactiveNodeState::Check {
             let sources = {
                 let sources = self.node_sources.borrow();
@@ -142,6 +289,104 @@ impl Runtime {
 
         // now we're clean
         self.mark_clean(node_id);
+
+        drop(updating);
+        if self.updating.borrow().is_empty() {
+            self.run_deferred_effects();
+        }
+    }
+
+    /// Re-runs effects that dirtied themselves while they were running, until
+    /// the graph settles or one of them exceeds the re-run limit.
+    fn run_deferred_effects(&self) {
+        if self.flushing_deferred.get() || self.deferred_effects.borrow().is_empty() {
+            return;
+        }
+
+        if self.batching.get() {
+            // the end of the batch will pick these up
+            let deferred = self.deferred_effects.take();
+            let mut nodes = self.nodes.borrow_mut();
+            let mut pending_effects = self.pending_effects.borrow_mut();
+            for (effect_id, _) in deferred {
+                if let Some(node) = nodes.get_mut(effect_id) {
+                    node.state = ReactiveNodeState::Dirty;
+                    pending_effects.push(effect_id);
+                }
+            }
+            return;
+        }
+
+        self.flushing_deferred.set(true);
+        let limit = self.max_effect_reruns.get();
+        let mut reruns = FxHashMap::<NodeId, usize>::default();
+        loop {
+            let deferred = self.deferred_effects.take();
+            if deferred.is_empty() {
+                break;
+            }
+
+            for (effect_id, cycle) in deferred {
+                let count = reruns.entry(effect_id).or_default();
+                *count += 1;
+                if *count > limit {
+                    self.flushing_deferred.set(false);
+                    self.report_error(ReactiveSystemError::EffectLimitExceeded {
+                        limit,
+                        cycle: self.locations(&cycle),
+                    });
+                }
+
+                if let Some(node) = self.nodes.borrow_mut().get_mut(effect_id) {
+                    node.state = ReactiveNodeState::Dirty;
+                }
+                if !self.park_if_paused(effect_id) {
+                    self.update_if_necessary(effect_id);
+                }
+            }
+        }
+        self.flushing_deferred.set(false);
+    }
+
+    fn is_effect(&self, node_id: NodeId) -> bool {
+        matches!(
+            self.nodes.borrow().get(node_id),
+            Some(ReactiveNode {
+                node_type: ReactiveNodeType::Effect { .. },
+                ..
+            })
+        )
+    }
+
+    /// Returns the locations at which the given nodes were created.
+    /// These are only tracked in debug mode.
+    pub(crate) fn locations(
+        &self,
+        nodes: &[NodeId],
+    ) -> Vec<&'static std::panic::Location<'static>> {
+        #[cfg(debug_assertions)]
+        {
+            let all_nodes = self.nodes.borrow();
+            nodes
+                .iter()
+                .filter_map(|node| all_nodes.get(*node).map(|node| node.defined_at))
+                .collect()
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            _ = nodes;
+            Vec::new()
+        }
+    }
+
+    /// Resets the update bookkeeping so the runtime stays usable if the
+    /// panic is caught, then panics with the given error.
+    #[cold]
+    #[inline(never)]
+    fn report_error(&self, error: ReactiveSystemError) -> ! {
+        self.updating.borrow_mut().clear();
+        self.deferred_effects.borrow_mut().clear();
+        panic!("{error}")
     }
 
     pub(crate) fn cleanup_node(&self, node_id: NodeId) {
@@ -178,13 +423,20 @@ impl Runtime {
                 ReactiveNodeType::Signal | ReactiveNodeType::Trigger => true,
                 ReactiveNodeType::Memo { ref f } | ReactiveNodeType::Effect { ref f } => {
                     let value = node.value();
+                    let sources = self.has_lazy_memos.get().then(|| self.sources(node_id));
                     // set this node as the observer
-                    self.with_observer(node_id, move || {
+                    let changed = self.with_observer(node_id, move || {
                         // clean up sources of this memo/effect
                         self.cleanup_sources(node_id);
 
                         f.run(value)
-                    })
+                    });
+                    // only once it has run again can we tell which of its old
+                    // sources it no longer depends on
+                    if let Some(sources) = sources {
+                        self.release_unobserved(sources);
+                    }
+                    changed
                 }
             };
 
@@ -238,18 +490,32 @@ impl Runtime {
                     }
                 }
 
+                self.paused_owners.borrow_mut().remove(&node);
+                self.effect_options.borrow_mut().remove(node);
+
                 // no longer needs to track its sources
+                let sources = self.has_lazy_memos.get().then(|| self.sources(node));
+                self.cleanup_sources(node);
                 self.node_sources.borrow_mut().remove(node);
+                if let Some(sources) = sources {
+                    self.release_unobserved(sources);
+                }
 
                 // remove the node from the graph
-                let node = { self.nodes.borrow_mut().remove(node) };
+                let node_id = node;
+                let node = { self.nodes.borrow_mut().remove(node_id) };
                 drop(node);
+                self.child_nodes.borrow_mut().swap_remove(&node_id);
             }
             ScopeProperty::Resource(id) => {
                 self.resources.borrow_mut().remove(id);
+                #[cfg(debug_assertions)]
+                self.resource_locations.borrow_mut().remove(id);
             }
             ScopeProperty::StoredValue(id) => {
                 self.stored_values.borrow_mut().remove(id);
+                #[cfg(debug_assertions)]
+                self.stored_value_locations.borrow_mut().remove(id);
             }
         }
     }
@@ -266,6 +532,58 @@ impl Runtime {
         }
     }
 
+    fn sources(&self, node_id: NodeId) -> Vec<NodeId> {
+        self.node_sources
+            .borrow()
+            .get(node_id)
+            .map(|sources| sources.borrow().iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether anything is subscribed to the given node.
+    pub(crate) fn is_observed(&self, node_id: NodeId) -> bool {
+        self.node_subscribers
+            .borrow()
+            .get(node_id)
+            .is_some_and(|subs| !subs.borrow().is_empty())
+    }
+
+    /// Releases the lazy memos among `nodes` that nothing subscribes to any
+    /// more, which in turn may leave their own sources unobserved.
+    fn release_unobserved(&self, nodes: Vec<NodeId>) {
+        for node_id in nodes {
+            if self.is_observed(node_id) {
+                continue;
+            }
+            let memo = match self.nodes.borrow().get(node_id) {
+                Some(ReactiveNode {
+                    value: Some(value),
+                    node_type: ReactiveNodeType::Memo { f },
+                    ..
+                }) => Some((Rc::clone(f), Rc::clone(value))),
+                _ => None,
+            };
+            let Some((f, value)) = memo else {
+                continue;
+            };
+            if !f.release(value) {
+                continue;
+            }
+
+            // it will be run from scratch the next time it is read
+            if let Some(node) = self.nodes.borrow_mut().get_mut(node_id) {
+                node.state = ReactiveNodeState::Dirty;
+            }
+            self.cleanup_node(node_id);
+            let sources = self.sources(node_id);
+            self.cleanup_sources(node_id);
+            if let Some(own_sources) = self.node_sources.borrow().get(node_id) {
+                own_sources.borrow_mut().clear();
+            }
+            self.release_unobserved(sources);
+        }
+    }
+
     fn current_state(&self, node: NodeId) -> ReactiveNodeState {
         match self.nodes.borrow().get(node) {
             None => ReactiveNodeState::Clean,
@@ -431,14 +749,84 @@ impl Runtime {
 
     pub(crate) fn run_effects(&self) {
         if !self.batching.get() {
-            let effects = self.pending_effects.take();
-            for effect_id in effects {
-                self.update_if_necessary(effect_id);
+            let deferred_phases = self.deferred_phases.borrow().clone();
+            self.run_pending_effects(|phase| !deferred_phases.contains(&phase));
+        }
+    }
+
+    /// Runs the pending effects of the phases `runnable` accepts, one phase
+    /// at a time and by priority within it, until none are left.
+    ///
+    /// When called while a phase is running, only that phase and earlier ones
+    /// run, and later ones are left for the outer call.
+    pub(crate) fn run_pending_effects(&self, runnable: impl Fn(EffectPhase) -> bool) {
+        let outer = self.draining_phase.get();
+        let _restore = RestoreDrainingPhase(self, outer);
+        let runnable = |phase| runnable(phase) && outer.is_none_or(|outer| phase <= outer);
+        loop {
+            let (mut due, waiting): (Vec<_>, Vec<_>) = self
+                .pending_effects
+                .take()
+                .into_iter()
+                .map(|effect_id| (self.effect_options(effect_id), effect_id))
+                .partition(|(options, _)| runnable(options.phase));
+            let Some(phase) = due.iter().map(|(options, _)| options.phase).min() else {
+                self.pending_effects
+                    .borrow_mut()
+                    .extend(waiting.into_iter().map(|(_, effect_id)| effect_id));
+                return;
+            };
+
+            // later phases wait, as running this one may change what they read
+            let later = due.iter().filter(|(options, _)| options.phase != phase);
+            self.pending_effects
+                .borrow_mut()
+                .extend(waiting.iter().chain(later).map(|(_, effect_id)| *effect_id));
+            due.retain(|(options, _)| options.phase == phase);
+            // a stable sort, so effects of the same priority keep their order
+            due.sort_by_key(|(options, _)| std::cmp::Reverse(options.priority));
+
+            self.draining_phase.set(Some(phase));
+            for (_, effect_id) in due {
+                if !self.park_if_paused(effect_id) {
+                    self.update_if_necessary(effect_id);
+                }
             }
         }
     }
 
+    fn effect_options(&self, effect_id: NodeId) -> EffectOptions {
+        self.effect_options
+            .borrow()
+            .get(effect_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether `node` belongs to a subtree that has been paused.
+    fn is_paused(&self, node: NodeId) -> bool {
+        let paused_owners = self.paused_owners.borrow();
+        if paused_owners.is_empty() {
+            return false;
+        }
+        let owners = self.node_owners.borrow();
+        std::iter::successors(Some(node), |node| owners.get(*node).copied())
+            .any(|node| paused_owners.contains(&node))
+    }
+
+    /// Keeps a dirty effect from running while it is paused, and returns
+    /// whether it was. It stays dirty, so it isn't queued again until it has
+    /// been resumed and run.
+    fn park_if_paused(&self, effect_id: NodeId) -> bool {
+        let paused = self.is_paused(effect_id);
+        if paused {
+            self.paused_effects.borrow_mut().insert(effect_id);
+        }
+        paused
+    }
+
     pub(crate) fn dispose_node(&self, node: NodeId) {
+        self.paused_owners.borrow_mut().remove(&node);
         self.node_sources.borrow_mut().remove(node);
         self.node_subscribers.borrow_mut().remove(node);
         self.nodes.borrow_mut().remove(node);
@@ -450,6 +838,19 @@ impl Runtime {
         property: ScopeProperty,
         #[cfg(debug_assertions)] defined_at: &'static std::panic::Location<'static>,
     ) {
+        #[cfg(debug_assertions)]
+        match property {
+            ScopeProperty::Resource(id) => {
+                self.resource_locations.borrow_mut().insert(id, defined_at);
+            }
+            ScopeProperty::StoredValue(id) => {
+                self.stored_value_locations
+                    .borrow_mut()
+                    .insert(id, defined_at);
+            }
+            _ => {}
+        }
+
         let mut properties = self.node_properties.borrow_mut();
         if let Some(owner) = self.owner.get() {
             if let Some(entry) = properties.entry(owner) {
@@ -647,10 +1048,13 @@ pub struct RuntimeId;
 ///
 /// ## Panics
 /// Panics if there is no current reactive runtime.
+#[track_caller]
 pub fn as_child_of_current_owner<T, U>(f: impl Fn(T) -> U) -> impl Fn(T) -> (U, Disposer)
 where
     T: 'static,
 {
+    #[cfg(debug_assertions)]
+    let defined_at = std::panic::Location::caller();
     let owner =
         with_runtime(|runtime| runtime.owner.get()).expect("runtime should be alive when created");
 
@@ -666,8 +1070,15 @@ where
                 value: None,
                 state: ReactiveNodeState::Clean,
                 node_type: ReactiveNodeType::Trigger,
+                #[cfg(debug_assertions)]
+                defined_at,
             });
-            runtime.push_scope_property(ScopeProperty::Trigger(id));
+            runtime.register_property(
+                ScopeProperty::Trigger(id),
+                #[cfg(debug_assertions)]
+                defined_at,
+            );
+            runtime.child_nodes.borrow_mut().insert(id);
             let disposer = Disposer(id);
 
             runtime.owner.set(Some(id));
@@ -723,14 +1134,101 @@ pub fn with_owner<T>(owner: Owner, f: impl FnOnce() -> T) -> T {
     try_with_owner(owner, f).unwrap()
 }
 
+/// Errors that can occur inside the reactive system.
 #[derive(Error, Debug)]
 pub enum ReactiveSystemError {
+    /// The runtime has already been disposed.
     #[error("Runtime {0:?} has been disposed.")]
     RuntimeDisposed(RuntimeId),
+    /// The owner has already been disposed.
     #[error("Owner {0:?} has been disposed.")]
     OwnerDisposed(Owner),
+    /// The runtime's nodes were already mutably borrowed.
     #[error("Error borrowing runtime.nodes {0:?}")]
     Borrow(std::cell::BorrowError),
+    /// A memo depends on itself, directly or through other memos.
+    ///
+    /// Contains the locations at which every node in the cycle was created,
+    /// starting with the node that was read again. Locations are only
+    /// tracked in debug mode.
+    #[error(
+        "Cycle detected in the reactive graph. The nodes in the cycle were \
+         created at:\n{}",
+        format_locations(.0)
+    )]
+    Cycle(Vec<&'static std::panic::Location<'static>>),
+    /// An effect kept re-triggering itself, and was re-run more than the
+    /// limit set with [`RuntimeId::set_max_effect_reruns`] in a single flush.
+    #[error(
+        "An effect was re-run more than {limit} times in a single update, \
+         which probably means it writes to a signal it (indirectly) reads. \
+         The nodes in the cycle were created at:\n{}",
+        format_locations(.cycle)
+    )]
+    EffectLimitExceeded {
+        /// The maximum number of re-runs that was exceeded.
+        limit: usize,
+        /// The locations at which every node in the cycle was created,
+        /// starting with the effect that was re-run.
+        cycle: Vec<&'static std::panic::Location<'static>>,
+    },
+    /// A reactive value was accessed after it had been disposed.
+    #[error(
+        "Attempted to access a {kind} after it was disposed.\n{}accessed \
+         here: {accessed_at}",
+        format_defined_at(*.kind, *.defined_at)
+    )]
+    Disposed {
+        /// What kind of value was accessed.
+        kind: ValueKind,
+        /// Where the value was created. This is only tracked in debug mode.
+        defined_at: Option<&'static std::panic::Location<'static>>,
+        /// Where the value was accessed.
+        accessed_at: &'static std::panic::Location<'static>,
+    },
+}
+
+impl ReactiveSystemError {
+    /// Creates a [`ReactiveSystemError::Disposed`] for an access happening
+    /// at the caller's location.
+    #[track_caller]
+    pub(crate) fn disposed(
+        kind: ValueKind,
+        #[cfg(any(debug_assertions, feature = "ssr"))] defined_at: &'static std::panic::Location<
+            'static,
+        >,
+    ) -> Self {
+        Self::Disposed {
+            kind,
+            #[cfg(any(debug_assertions, feature = "ssr"))]
+            defined_at: Some(defined_at),
+            #[cfg(not(any(debug_assertions, feature = "ssr")))]
+            defined_at: None,
+            accessed_at: std::panic::Location::caller(),
+        }
+    }
+}
+
+fn format_defined_at(
+    kind: ValueKind,
+    defined_at: Option<&'static std::panic::Location<'static>>,
+) -> String {
+    match defined_at {
+        Some(defined_at) => format!("{kind} created here: {defined_at}\n"),
+        None => String::new(),
+    }
+}
+
+fn format_locations(locations: &[&'static std::panic::Location<'static>]) -> String {
+    if locations.is_empty() {
+        return "  (locations are only tracked in debug mode)".to_string();
+    }
+
+    locations
+        .iter()
+        .map(|location| format!("  - {location}"))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Runs the given code with the given reactive owner.
@@ -763,7 +1261,10 @@ pub fn try_with_owner<T>(owner: Owner, f: impl FnOnce() -> T) -> Result<T, React
 }
 
 /// Runs the given function as a child of the current Owner, once.
+#[track_caller]
 pub fn run_as_child<T>(f: impl FnOnce() -> T + 'static) -> T {
+    #[cfg(debug_assertions)]
+    let defined_at = std::panic::Location::caller();
     let owner =
         with_runtime(|runtime| runtime.owner.get()).expect("runtime should be alive when created");
     let (value, disposer) = with_runtime(|runtime| {
@@ -777,8 +1278,14 @@ pub fn run_as_child<T>(f: impl FnOnce() -> T + 'static) -> T {
             value: None,
             state: ReactiveNodeState::Clean,
             node_type: ReactiveNodeType::Trigger,
+            #[cfg(debug_assertions)]
+            defined_at,
         });
-        runtime.push_scope_property(ScopeProperty::Trigger(id));
+        runtime.register_property(
+            ScopeProperty::Trigger(id),
+            #[cfg(debug_assertions)]
+            defined_at,
+        );
         let disposer = Disposer(id);
 
         runtime.owner.set(Some(id));
@@ -799,13 +1306,38 @@ pub fn run_as_child<T>(f: impl FnOnce() -> T + 'static) -> T {
 impl RuntimeId {
     /// Removes the runtime, disposing of everything created in it.
     ///
+    /// In debug mode, this warns about every child created with
+    /// [`as_child_of_current_owner`] that is still alive, since its
+    /// [`Disposer`] was never dropped. Use [`RuntimeId::alive_values`] to
+    /// list everything else that is still alive.
+    ///
     /// ## Panics
     /// Panics if the reactive runtime you’re trying to dispose is not found.
     /// This would suggest either that you’re trying to dispose of it twice, or
     /// that it was created in a different thread; panicking here indicates a
     /// memory leak.
     pub fn dispose(self) {
-        let _ = self;
+        self.warn_leaked_children();
+    }
+
+    /// Sets how many times a single effect may be re-run while flushing one
+    /// update before the runtime gives up with
+    /// [`ReactiveSystemError::EffectLimitExceeded`].
+    ///
+    /// An effect is re-run this way when it causes itself to be marked dirty
+    /// while it is still running, for example by writing to a signal that
+    /// another effect reads before writing to a signal the first effect reads.
+    /// Effects that settle after a few rounds are fine; ones that never do
+    /// would otherwise loop forever.
+    ///
+    /// Defaults to [`DEFAULT_MAX_EFFECT_RERUNS`].
+    pub fn set_max_effect_reruns(self, limit: usize) {
+        _ = with_runtime(|runtime| runtime.max_effect_reruns.set(limit));
+    }
+
+    /// Returns the limit set by [`RuntimeId::set_max_effect_reruns`].
+    pub fn max_effect_reruns(self) -> usize {
+        with_runtime(|runtime| runtime.max_effect_reruns.get()).unwrap_or(DEFAULT_MAX_EFFECT_RERUNS)
     }
 
     #[cfg_attr(
@@ -847,13 +1379,21 @@ impl RuntimeId {
     #[track_caller]
     #[inline(always)] // only because it's placed here to fit in with the other create methods
     pub(crate) fn create_trigger(self) -> Trigger {
+        #[cfg(debug_assertions)]
+        let defined_at = std::panic::Location::caller();
         let id = with_runtime(|runtime| {
             let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: None,
                 state: ReactiveNodeState::Clean,
                 node_type: ReactiveNodeType::Trigger,
+                #[cfg(debug_assertions)]
+                defined_at,
             });
-            runtime.push_scope_property(ScopeProperty::Trigger(id));
+            runtime.register_property(
+                ScopeProperty::Trigger(id),
+                #[cfg(debug_assertions)]
+                defined_at,
+            );
             id
         })
         .expect("tried to create a trigger in a runtime that has been disposed");
@@ -865,14 +1405,23 @@ impl RuntimeId {
         }
     }
 
+    #[track_caller]
     pub(crate) fn create_concrete_signal(self, value: Rc<RefCell<dyn Any>>) -> NodeId {
+        #[cfg(debug_assertions)]
+        let defined_at = std::panic::Location::caller();
         with_runtime(|runtime| {
             let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: Some(value),
                 state: ReactiveNodeState::Clean,
                 node_type: ReactiveNodeType::Signal,
+                #[cfg(debug_assertions)]
+                defined_at,
             });
-            runtime.push_scope_property(ScopeProperty::Signal(id));
+            runtime.register_property(
+                ScopeProperty::Signal(id),
+                #[cfg(debug_assertions)]
+                defined_at,
+            );
             id
         })
         .expect("tried to create a signal in a runtime that has been disposed")
@@ -917,11 +1466,14 @@ impl RuntimeId {
         }
     }
 
+    #[track_caller]
     pub(crate) fn create_concrete_effect(
         self,
         value: Rc<RefCell<dyn Any>>,
         effect: Rc<dyn AnyComputation>,
     ) -> NodeId {
+        #[cfg(debug_assertions)]
+        let defined_at = std::panic::Location::caller();
         with_runtime(|runtime| {
             let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: Some(Rc::clone(&value)),
@@ -929,18 +1481,27 @@ impl RuntimeId {
                 node_type: ReactiveNodeType::Effect {
                     f: Rc::clone(&effect),
                 },
+                #[cfg(debug_assertions)]
+                defined_at,
             });
-            runtime.push_scope_property(ScopeProperty::Effect(id));
+            runtime.register_property(
+                ScopeProperty::Effect(id),
+                #[cfg(debug_assertions)]
+                defined_at,
+            );
             id
         })
         .expect("tried to create an effect in a runtime that has been disposed")
     }
 
+    #[track_caller]
     pub(crate) fn create_concrete_memo(
         self,
         value: Rc<RefCell<dyn Any>>,
         computation: Rc<dyn AnyComputation>,
     ) -> NodeId {
+        #[cfg(debug_assertions)]
+        let defined_at = std::panic::Location::caller();
         with_runtime(|runtime| {
             let id = runtime.nodes.borrow_mut().insert(ReactiveNode {
                 value: Some(value),
@@ -948,8 +1509,14 @@ impl RuntimeId {
                 // will be run the first time we ask for it
                 state: ReactiveNodeState::Dirty,
                 node_type: ReactiveNodeType::Memo { f: computation },
+                #[cfg(debug_assertions)]
+                defined_at,
             });
-            runtime.push_scope_property(ScopeProperty::Effect(id));
+            runtime.register_property(
+                ScopeProperty::Effect(id),
+                #[cfg(debug_assertions)]
+                defined_at,
+            );
             id
         })
         .expect("tried to create a memo in a runtime that has been disposed")
@@ -972,6 +1539,7 @@ impl RuntimeId {
         )
     }
 
+    #[track_caller]
     pub(crate) fn watch<W, T>(
         self,
         deps: impl Fn() -> W + 'static,
@@ -1046,15 +1614,25 @@ impl RuntimeId {
 
     #[track_caller]
     #[inline(always)]
-    pub(crate) fn create_memo<T>(self, f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
+    pub(crate) fn create_memo<T>(
+        self,
+        f: impl Fn(Option<&T>) -> T + 'static,
+        eq: impl Fn(&T, &T) -> bool + 'static,
+        lazy: bool,
+    ) -> Memo<T>
     where
-        T: PartialEq + Any + 'static,
+        T: Any + 'static,
     {
+        if lazy {
+            _ = with_runtime(|runtime| runtime.has_lazy_memos.set(true));
+        }
         Memo {
             id: self.create_concrete_memo(
                 Rc::new(RefCell::new(None::<T>)),
                 Rc::new(MemoState {
                     f,
+                    eq,
+                    lazy,
                     t: PhantomData,
                     #[cfg(any(debug_assertions, feature = "ssr"))]
                     defined_at: std::panic::Location::caller(),
@@ -1073,6 +1651,8 @@ impl Runtime {
             value: None,
             state: ReactiveNodeState::Clean,
             node_type: ReactiveNodeType::Trigger,
+            #[cfg(debug_assertions)]
+            defined_at: std::panic::Location::caller(),
         };
         let mut nodes: SlotMap<NodeId, ReactiveNode> = SlotMap::default();
         let root_id = nodes.insert(root);
@@ -1080,6 +1660,8 @@ impl Runtime {
         Self {
             owner: Cell::new(Some(root_id)),
             nodes: RefCell::new(nodes),
+            max_effect_reruns: Cell::new(DEFAULT_MAX_EFFECT_RERUNS),
+            root: root_id,
             ..Self::default()
         }
     }
@@ -1304,7 +1886,7 @@ fn push_cleanup(cleanup_fn: Box<dyn FnOnce()>) {
     });
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum ScopeProperty {
     Trigger(NodeId),
     Signal(NodeId),
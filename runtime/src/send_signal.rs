@@ -0,0 +1,223 @@
+use crate::{
+    batch, on_cleanup,
+    runtime::{with_runtime, RuntimeId},
+    SignalSet, SignalUpdate,
+};
+use rustc_hash::FxHashMap;
+use std::{
+    any::Any,
+    fmt,
+    marker::PhantomData,
+    rc::Rc,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+/// The setters of every [`SendSignal`], and the writes other threads have
+/// queued for them.
+#[derive(Default)]
+pub(crate) struct SyncWrites {
+    next_id: u64,
+    setters: FxHashMap<u64, Rc<dyn Fn(Write)>>,
+    queue: Arc<SyncQueue>,
+}
+
+/// A [`SyncWrite`] to a signal of any type.
+type Write = Box<dyn Any + Send>;
+
+/// A write to the signal with the given id.
+type QueuedWrite = (u64, Write);
+
+#[derive(Default)]
+struct SyncQueue {
+    writes: Mutex<Vec<QueuedWrite>>,
+    /// Tells whatever drives the runtime that there are writes to apply.
+    waker: Mutex<Option<Box<dyn Fn() + Send>>>,
+}
+
+impl SyncQueue {
+    fn writes(&self) -> MutexGuard<'_, Vec<QueuedWrite>> {
+        // a write can't be left half done, so a panic elsewhere doesn't
+        // matter here
+        self.writes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, id: u64, write: Write) {
+        let was_empty = {
+            let mut writes = self.writes();
+            writes.push((id, write));
+            writes.len() == 1
+        };
+        // the runtime only needs to be woken once for every drain
+        if was_empty {
+            let waker = self.waker.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(wake) = &*waker {
+                wake();
+            }
+        }
+    }
+}
+
+enum SyncWrite<T> {
+    Set(T),
+    Update(Box<dyn FnOnce(&mut T) + Send>),
+}
+
+/// A handle to a signal that can be moved to other threads, created with
+/// [`create_sync_setter`].
+///
+/// Writes made through it are queued, and applied on the runtime's own
+/// thread the next time [`RuntimeId::run_sync_writes`] is called. Writes
+/// made after the signal has been disposed are dropped.
+pub struct SendSignal<T> {
+    id: u64,
+    queue: Arc<SyncQueue>,
+    ty: PhantomData<fn(T)>,
+}
+
+/// Creates a [`SendSignal`], which is `Send` and `Sync`, so that other
+/// threads can write to `signal`.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let (progress, set_progress) = create_signal(0);
+/// let sync_progress = create_sync_setter(set_progress);
+///
+/// std::thread::spawn(move || {
+///     for done in 1..=10 {
+///         sync_progress.set(done * 10);
+///     }
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(progress.get(), 0);
+/// runtime.run_sync_writes();
+/// assert_eq!(progress.get(), 100);
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_sync_setter<S, T>(signal: S) -> SendSignal<T>
+where
+    S: SignalSet<Value = T> + SignalUpdate<Value = T> + 'static,
+    T: Send + 'static,
+{
+    let setter = Rc::new(move |write: Write| {
+        let Ok(write) = write.downcast::<SyncWrite<T>>() else {
+            return;
+        };
+        match *write {
+            SyncWrite::Set(value) => _ = signal.try_set(value),
+            SyncWrite::Update(f) => _ = signal.try_update(f),
+        }
+    });
+    register(setter)
+}
+
+/// Creates a [`SendSignal`] whose values are passed to `f` on the runtime's
+/// own thread, rather than set on a signal. Updates are ignored.
+pub(crate) fn create_sync_handler<T>(f: impl Fn(T) + 'static) -> SendSignal<T>
+where
+    T: Send + 'static,
+{
+    register(Rc::new(move |write: Write| {
+        if let Ok(write) = write.downcast::<SyncWrite<T>>() {
+            if let SyncWrite::Set(value) = *write {
+                f(value);
+            }
+        }
+    }))
+}
+
+#[track_caller]
+fn register<T>(setter: Rc<dyn Fn(Write)>) -> SendSignal<T> {
+    let (id, queue) = with_runtime(|runtime| {
+        let mut sync_writes = runtime.sync_writes.borrow_mut();
+        let id = sync_writes.next_id;
+        sync_writes.next_id += 1;
+        sync_writes.setters.insert(id, setter);
+        (id, Arc::clone(&sync_writes.queue))
+    })
+    .expect("tried to create a SendSignal in a runtime that has been disposed");
+
+    on_cleanup(move || {
+        _ = with_runtime(|runtime| runtime.sync_writes.borrow_mut().setters.remove(&id));
+    });
+
+    SendSignal {
+        id,
+        queue,
+        ty: PhantomData,
+    }
+}
+
+impl<T: Send + 'static> SendSignal<T> {
+    /// Queues setting the signal to `value`.
+    pub fn set(&self, value: T) {
+        self.queue.push(self.id, Box::new(SyncWrite::Set(value)));
+    }
+
+    /// Queues updating the value of the signal with `f`.
+    pub fn update(&self, f: impl FnOnce(&mut T) + Send + 'static) {
+        self.queue
+            .push(self.id, Box::new(SyncWrite::Update(Box::new(f))));
+    }
+}
+
+impl<T> Clone for SendSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            queue: Arc::clone(&self.queue),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for SendSignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendSignal").field("id", &self.id).finish()
+    }
+}
+
+impl RuntimeId {
+    /// Applies every write queued by a [`SendSignal`], in a single
+    /// [`batch`], and returns how many there were.
+    ///
+    /// Whatever drives the runtime should call this when the function set
+    /// with [`set_sync_waker`](RuntimeId::set_sync_waker) is called.
+    pub fn run_sync_writes(self) -> usize {
+        let Ok(queue) = with_runtime(|runtime| Arc::clone(&runtime.sync_writes.borrow().queue))
+        else {
+            return 0;
+        };
+        let writes = std::mem::take(&mut *queue.writes());
+        let count = writes.len();
+        batch(|| {
+            for (id, write) in writes {
+                // setting a signal can create or dispose others
+                let setter =
+                    with_runtime(|runtime| runtime.sync_writes.borrow().setters.get(&id).cloned())
+                        .ok()
+                        .flatten();
+                if let Some(setter) = setter {
+                    setter(write);
+                }
+            }
+        });
+        count
+    }
+
+    /// Sets the function a [`SendSignal`] calls, on whatever thread it is
+    /// written from, when there are new writes to
+    /// [run](RuntimeId::run_sync_writes).
+    ///
+    /// It is called once for the first write after every run, rather than
+    /// for every write.
+    pub fn set_sync_waker(self, wake: impl Fn() + Send + 'static) {
+        _ = with_runtime(|runtime| {
+            let queue = Arc::clone(&runtime.sync_writes.borrow().queue);
+            *queue.waker.lock().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(wake));
+        });
+    }
+}
@@ -1,3 +1,4 @@
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine as _};
 use cfg_if::cfg_if;
 use std::rc::Rc;
 use thiserror::Error;
@@ -15,16 +16,17 @@ pub enum SerializationError {
     Deserialize(Rc<dyn std::error::Error>),
 }
 
-/// Describes an object that can be serialized to or from a supported format
-/// Currently those are JSON and Cbor
+/// Describes an object that can be serialized to or from a string, in the
+/// crate-wide [`DefaultFormat`].
 ///
 /// This is primarily used for serializing and deserializing [`Resource`](crate::Resource)s
 /// so they can begin on the server and be resolved on the client, but can be used
 /// for any data that needs to be serialized/deserialized.
 ///
 /// This trait is intended to abstract over various serialization crates,
-/// as selected between by the crate features `serde` (default), `serde-lite`,
-/// and `miniserde`.
+/// as selected between by the crate features `rkyv`, `miniserde` and
+/// `serde-lite`, defaulting to `serde` with JSON. To use a different format
+/// for a single value, use its [`Codec`] instead.
 pub trait Serializable
 where
     Self: Sized,
@@ -36,86 +38,199 @@ where
     fn de(bytes: &str) -> Result<Self, SerializationError>;
 }
 
-cfg_if! {
-    if #[cfg(feature = "rkyv")] {
-        use rkyv::{Archive, CheckBytes, Deserialize, Serialize, ser::serializers::AllocSerializer, de::deserializers::SharedDeserializeMap, validation::validators::DefaultValidator};
-        use base64::Engine as _;
-        use base64::engine::general_purpose::STANDARD_NO_PAD;
-
-        impl<T> Serializable for T
-        where
-        T: Serialize<AllocSerializer<1024>>,
-        T: Archive,
-        T::Archived: for<'b> CheckBytes<DefaultValidator<'b>> + Deserialize<T, SharedDeserializeMap>,
-        {
-            fn ser(&self) -> Result<String, SerializationError> {
-                let bytes = rkyv::to_bytes::<T, 1024>(self).map_err(|e| SerializationError::Serialize(Rc::new(e)))?;
-                Ok(STANDARD_NO_PAD.encode(bytes))
-            }
-
-            fn de(serialized: &str) -> Result<Self, SerializationError> {
-                let bytes = STANDARD_NO_PAD.decode(serialized.as_bytes()).map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
-                rkyv::from_bytes::<T>(&bytes).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
-            }
+impl<T> Serializable for T
+where
+    DefaultFormat: Codec<T>,
+{
+    fn ser(&self) -> Result<String, SerializationError> {
+        DefaultFormat::encode_string(self)
+    }
+
+    fn de(serialized: &str) -> Result<Self, SerializationError> {
+        DefaultFormat::decode_str(serialized)
+    }
+}
+
+/// A format that values of type `T` can be encoded in.
+///
+/// Formats are zero-sized types, so the one to use can be picked for each
+/// call:
+///
+/// ```
+/// # use goober_runtime::*;
+/// let point = (3u16, -4i32);
+/// let json = Json::encode_string(&point).unwrap();
+/// assert_eq!(json, "[3,-4]");
+///
+/// # #[cfg(feature = "postcard")] {
+/// let bytes = Postcard::encode(&point).unwrap();
+/// assert_eq!(bytes, [3, 7]);
+/// assert_eq!(Postcard::decode(&bytes).ok(), Some(point));
+/// # }
+/// ```
+pub trait Codec<T> {
+    /// Whether the format is binary rather than text, in which case
+    /// [`encode_string`](Codec::encode_string) encodes it as base64.
+    const BINARY: bool;
+
+    /// Encodes `value` into bytes.
+    fn encode(value: &T) -> Result<Vec<u8>, SerializationError>;
+
+    /// Decodes a value from bytes produced by [`encode`](Codec::encode).
+    fn decode(bytes: &[u8]) -> Result<T, SerializationError>;
+
+    /// Encodes `value` into a string.
+    fn encode_string(value: &T) -> Result<String, SerializationError> {
+        let bytes = Self::encode(value)?;
+        if Self::BINARY {
+            Ok(STANDARD_NO_PAD.encode(bytes))
+        } else {
+            String::from_utf8(bytes).map_err(|e| SerializationError::Serialize(Rc::new(e)))
         }
     }
-    // prefer miniserde if it's chosen
-    else if #[cfg(feature = "miniserde")] {
-        use miniserde::{json, Deserialize, Serialize};
-
-        impl<T> Serializable for T
-        where
-            T: Serialize + Deserialize,
-        {
-            fn ser(&self) -> Result<String, SerializationError> {
-                Ok(json::to_string(&self))
-            }
-
-            fn de(json: &str) -> Result<Self, SerializationError> {
-                json::from_str(json).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
-            }
+
+    /// Decodes a value from a string produced by
+    /// [`encode_string`](Codec::encode_string).
+    fn decode_str(serialized: &str) -> Result<T, SerializationError> {
+        if Self::BINARY {
+            let bytes = STANDARD_NO_PAD
+                .decode(serialized.as_bytes())
+                .map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
+            Self::decode(&bytes)
+        } else {
+            Self::decode(serialized.as_bytes())
         }
+    }
+}
 
+cfg_if! {
+    if #[cfg(feature = "rkyv")] {
+        /// The format [`Serializable`] uses, picked by the crate features.
+        pub type DefaultFormat = Rkyv;
+    } else if #[cfg(feature = "miniserde")] {
+        /// The format [`Serializable`] uses, picked by the crate features.
+        pub type DefaultFormat = Miniserde;
+    } else if #[cfg(feature = "serde-lite")] {
+        /// The format [`Serializable`] uses, picked by the crate features.
+        pub type DefaultFormat = SerdeLite;
+    } else {
+        /// The format [`Serializable`] uses, picked by the crate features.
+        pub type DefaultFormat = Json;
     }
-    // use serde-lite if enabled
-    else if #[cfg(feature = "serde-lite")] {
-        use serde_lite::{Deserialize, Serialize};
-
-        impl<T> Serializable for T
-        where
-            T: Serialize + Deserialize,
-        {
-            fn ser(&self) -> Result<String, SerializationError> {
-                let intermediate = self
-                    .serialize()
-                    .map_err(|e| SerializationError::Serialize(Rc::new(e)))?;
-                serde_json::to_string(&intermediate).map_err(|e| SerializationError::Serialize(Rc::new(e)))
-            }
-
-            fn de(json: &str) -> Result<Self, SerializationError> {
-                let intermediate =
-                    serde_json::from_str(json).map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
-                Self::deserialize(&intermediate).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
-            }
-        }
+}
+
+/// JSON, using `serde`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Json;
+
+impl<T> Codec<T> for Json
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    const BINARY: bool = false;
 
+    fn encode(value: &T) -> Result<Vec<u8>, SerializationError> {
+        serde_json::to_vec(value).map_err(|e| SerializationError::Serialize(Rc::new(e)))
     }
-    // otherwise, or if serde is chosen, default to serde
-    else {
-        use serde::{de::DeserializeOwned, Serialize};
-
-        impl<T> Serializable for T
-        where
-            T: DeserializeOwned + Serialize,
-        {
-            fn ser(&self) -> Result<String, SerializationError> {
-                serde_json::to_string(&self).map_err(|e| SerializationError::Serialize(Rc::new(e)))
-            }
-
-            fn de(json: &str) -> Result<Self, SerializationError> {
-                serde_json::from_str(json).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
-            }
 
-        }
+    fn decode(bytes: &[u8]) -> Result<T, SerializationError> {
+        serde_json::from_slice(bytes).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+    }
+}
+
+/// [Postcard](https://postcard.jamesmunns.com), a compact binary format,
+/// using `serde`. Needs the `postcard` feature.
+#[cfg(feature = "postcard")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl<T> Codec<T> for Postcard
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    const BINARY: bool = true;
+
+    fn encode(value: &T) -> Result<Vec<u8>, SerializationError> {
+        postcard::to_allocvec(value).map_err(|e| SerializationError::Serialize(Rc::new(e)))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, SerializationError> {
+        postcard::from_bytes(bytes).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+    }
+}
+
+/// The zero-copy binary format of `rkyv`. Needs the `rkyv` feature.
+#[cfg(feature = "rkyv")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rkyv;
+
+#[cfg(feature = "rkyv")]
+impl<T> Codec<T> for Rkyv
+where
+    T: rkyv::Serialize<rkyv::ser::serializers::AllocSerializer<1024>>,
+    T: rkyv::Archive,
+    T::Archived: for<'b> rkyv::CheckBytes<rkyv::validation::validators::DefaultValidator<'b>>
+        + rkyv::Deserialize<T, rkyv::de::deserializers::SharedDeserializeMap>,
+{
+    const BINARY: bool = true;
+
+    fn encode(value: &T) -> Result<Vec<u8>, SerializationError> {
+        rkyv::to_bytes::<T, 1024>(value)
+            .map(|bytes| bytes.into_vec())
+            .map_err(|e| SerializationError::Serialize(Rc::new(e)))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, SerializationError> {
+        rkyv::from_bytes::<T>(bytes).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+    }
+}
+
+/// JSON, using `miniserde`. Needs the `miniserde` feature.
+#[cfg(feature = "miniserde")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Miniserde;
+
+#[cfg(feature = "miniserde")]
+impl<T> Codec<T> for Miniserde
+where
+    T: miniserde::Serialize + miniserde::Deserialize,
+{
+    const BINARY: bool = false;
+
+    fn encode(value: &T) -> Result<Vec<u8>, SerializationError> {
+        Ok(miniserde::json::to_string(value).into_bytes())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, SerializationError> {
+        let json =
+            std::str::from_utf8(bytes).map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
+        miniserde::json::from_str(json).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+    }
+}
+
+/// JSON, using `serde-lite`. Needs the `serde-lite` feature.
+#[cfg(feature = "serde-lite")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SerdeLite;
+
+#[cfg(feature = "serde-lite")]
+impl<T> Codec<T> for SerdeLite
+where
+    T: serde_lite::Serialize + serde_lite::Deserialize,
+{
+    const BINARY: bool = false;
+
+    fn encode(value: &T) -> Result<Vec<u8>, SerializationError> {
+        let intermediate = value
+            .serialize()
+            .map_err(|e| SerializationError::Serialize(Rc::new(e)))?;
+        serde_json::to_vec(&intermediate).map_err(|e| SerializationError::Serialize(Rc::new(e)))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T, SerializationError> {
+        let intermediate = serde_json::from_slice(bytes)
+            .map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
+        T::deserialize(&intermediate).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     console_warn, create_isomorphic_effect, diagnostics, diagnostics::*, macros::debug_warn,
-    node::NodeId, on_cleanup, runtime::with_runtime, Runtime,
+    node::NodeId, on_cleanup, runtime::with_runtime, ReactiveSystemError, Runtime, ValueKind,
 };
 use futures::Stream;
 use std::{
@@ -706,27 +706,13 @@ where
     #[track_caller]
     #[inline(always)]
     pub(crate) fn with_no_subscription<U>(&self, f: impl FnOnce(&T) -> U) -> U {
-        #[cfg(debug_assertions)]
-        let caller = std::panic::Location::caller();
-
-        self.id
-            .try_with_no_subscription_by_id(f)
-            .unwrap_or_else(|_| {
-                #[cfg(not(debug_assertions))]
-                {
-                    panic!("tried to access ReadSignal that has been disposed")
-                }
-                #[cfg(debug_assertions)]
-                {
-                    panic!(
-                        "at {}, tried to access ReadSignal<{}> defined at {}, \
-                         but it has already been disposed",
-                        caller,
-                        std::any::type_name::<T>(),
-                        self.defined_at
-                    )
-                }
-            })
+        match self.id.try_with_no_subscription_by_id(f) {
+            Ok(value) => value,
+            Err(_) => panic_getting_dead_signal(
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            ),
+        }
     }
 
     /// Applies the function to the current Signal, if it exists, and subscribes
@@ -742,6 +728,21 @@ where
             Err(_) => Err(SignalError::RuntimeDisposed),
         }
     }
+
+    /// Returns [`ReactiveSystemError::Disposed`], naming where the signal was
+    /// created, if it has already been disposed.
+    #[track_caller]
+    pub fn check_alive(&self) -> Result<(), ReactiveSystemError> {
+        if with_runtime(|runtime| runtime.nodes.borrow().contains_key(self.id)).unwrap_or(false) {
+            Ok(())
+        } else {
+            Err(ReactiveSystemError::disposed(
+                ValueKind::Signal,
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            ))
+        }
+    }
 }
 
 impl<T> Clone for ReadSignal<T> {
@@ -1789,6 +1790,21 @@ impl<T> RwSignal<T> {
         create_rw_signal(value)
     }
 
+    /// Returns [`ReactiveSystemError::Disposed`], naming where the signal was
+    /// created, if it has already been disposed.
+    #[track_caller]
+    pub fn check_alive(&self) -> Result<(), ReactiveSystemError> {
+        if with_runtime(|runtime| runtime.nodes.borrow().contains_key(self.id)).unwrap_or(false) {
+            Ok(())
+        } else {
+            Err(ReactiveSystemError::disposed(
+                ValueKind::Signal,
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            ))
+        }
+    }
+
     /// Returns a read-only handle to the signal.
     ///
     /// Useful if you're trying to give read access to another component but ensure that it can't write
@@ -2191,8 +2207,8 @@ pub(crate) fn panic_getting_dead_signal(
 ) -> ! {
     panic!(
         "{}",
-        format_signal_warning(
-            "Attempted to get a signal after it was disposed.",
+        ReactiveSystemError::disposed(
+            ValueKind::Signal,
             #[cfg(any(debug_assertions, feature = "ssr"))]
             defined_at,
         )
@@ -0,0 +1,230 @@
+use crate::{
+    batch, on_cleanup,
+    runtime::{with_runtime, RuntimeId},
+    serialization::{Serializable, SerializationError},
+    SignalSet, SignalWithUntracked,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, rc::Rc, str::FromStr};
+
+/// The signals registered with [`register_signal`], by key.
+#[derive(Default)]
+pub(crate) struct Registry {
+    next_id: u64,
+    entries: BTreeMap<String, Entry>,
+    /// Values restored from a [`Snapshot`] whose signals haven't been
+    /// registered yet.
+    pending: BTreeMap<String, String>,
+}
+
+struct Entry {
+    // tells a registration apart from a later one under the same key
+    id: u64,
+    signal: Rc<dyn Registered>,
+}
+
+/// A signal registered with [`register_signal`].
+trait Registered {
+    /// Serializes the value, or returns `None` if the signal is disposed.
+    fn ser(&self) -> Option<Result<String, SerializationError>>;
+
+    fn restore(&self, serialized: &str) -> Result<(), SerializationError>;
+}
+
+struct RegisteredSignal<S>(S);
+
+impl<S, T> Registered for RegisteredSignal<S>
+where
+    S: SignalWithUntracked<Value = T> + SignalSet<Value = T>,
+    T: Serializable,
+{
+    fn ser(&self) -> Option<Result<String, SerializationError>> {
+        self.0.try_with_untracked(T::ser)
+    }
+
+    fn restore(&self, serialized: &str) -> Result<(), SerializationError> {
+        self.0.try_set(T::de(serialized)?);
+        Ok(())
+    }
+}
+
+/// Registers `signal` under `key`, so that its value is included in every
+/// [snapshot](RuntimeId::snapshot) until the current owner is cleaned up.
+///
+/// If a restored snapshot has a value for `key` that no signal has taken
+/// yet, `signal` is set to it, so a snapshot can be restored before the
+/// signals it is for have been created.
+///
+/// Registering another signal under the same `key` replaces this one.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let zoom = create_rw_signal(1.0);
+/// register_signal("editor.zoom", zoom);
+///
+/// zoom.set(1.5);
+/// let snapshot = runtime.snapshot();
+///
+/// zoom.set(2.0);
+/// runtime.restore(&snapshot);
+/// assert_eq!(zoom.get(), 1.5);
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn register_signal<S, T>(key: impl Into<String>, signal: S)
+where
+    S: SignalWithUntracked<Value = T> + SignalSet<Value = T> + 'static,
+    T: Serializable + 'static,
+{
+    let key = key.into();
+    let signal = Rc::new(RegisteredSignal(signal));
+    let Ok((id, pending)) = with_runtime(|runtime| {
+        let mut registry = runtime.registry.borrow_mut();
+        let id = registry.next_id;
+        registry.next_id += 1;
+        let entry = Entry {
+            id,
+            signal: Rc::clone(&signal) as Rc<dyn Registered>,
+        };
+        if registry.entries.insert(key.clone(), entry).is_some() {
+            crate::macros::debug_warn!(
+                "a signal was registered under {key:?}, which replaced the one already \
+                 registered under it"
+            );
+        }
+        (id, registry.pending.remove(&key))
+    }) else {
+        return;
+    };
+
+    if let Some(pending) = pending {
+        restore_value(&key, &*signal, &pending);
+    }
+
+    on_cleanup(move || {
+        _ = with_runtime(|runtime| {
+            let mut registry = runtime.registry.borrow_mut();
+            if registry
+                .entries
+                .get(&key)
+                .is_some_and(|entry| entry.id == id)
+            {
+                registry.entries.remove(&key);
+            }
+        });
+    });
+}
+
+fn restore_value(key: &str, signal: &dyn Registered, serialized: &str) {
+    if let Err(e) = signal.restore(serialized) {
+        crate::macros::debug_warn!("could not restore the value of {key:?}: {e}");
+    }
+}
+
+/// The values of every signal registered with [`register_signal`], taken
+/// with [`RuntimeId::snapshot`].
+///
+/// A snapshot is turned into a single document with
+/// [`to_string`](ToString::to_string), and back with [`str::parse`]. Each
+/// value is serialized with [`Serializable`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Snapshot {
+    values: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    /// The serialized value saved under `key`, if there is one.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// The keys that have a value in the snapshot.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    /// Whether the snapshot has no values at all.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let document = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&document)
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = SerializationError;
+
+    fn from_str(document: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(document).map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+    }
+}
+
+impl RuntimeId {
+    /// Takes a [`Snapshot`] of every signal registered with
+    /// [`register_signal`].
+    ///
+    /// Values that were restored but haven't been taken by a signal yet are
+    /// kept as they are. Values that can't be serialized are left out.
+    pub fn snapshot(self) -> Snapshot {
+        let (mut values, signals) = with_runtime(|runtime| {
+            let registry = runtime.registry.borrow();
+            let signals = registry
+                .entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), Rc::clone(&entry.signal)))
+                .collect::<Vec<_>>();
+            (registry.pending.clone(), signals)
+        })
+        .unwrap_or_default();
+
+        // serializing can read other signals, so the registry isn't borrowed
+        for (key, signal) in signals {
+            match signal.ser() {
+                Some(Ok(serialized)) => {
+                    values.insert(key, serialized);
+                }
+                Some(Err(e)) => {
+                    crate::macros::debug_warn!("could not take a snapshot of {key:?}: {e}")
+                }
+                None => {}
+            }
+        }
+        Snapshot { values }
+    }
+
+    /// Sets every registered signal to its value in `snapshot`, in a single
+    /// [`batch`], so that effects only see the restored state as a whole.
+    ///
+    /// Values for keys that have no signal yet are kept, and given to the
+    /// signal registered under that key later. Values that can't be
+    /// deserialized are skipped, and the signal keeps its current value.
+    pub fn restore(self, snapshot: &Snapshot) {
+        let signals = with_runtime(|runtime| {
+            let mut registry = runtime.registry.borrow_mut();
+            let mut signals = Vec::new();
+            for (key, serialized) in &snapshot.values {
+                match registry.entries.get(key) {
+                    Some(entry) => signals.push((key, Rc::clone(&entry.signal), serialized)),
+                    None => {
+                        registry.pending.insert(key.clone(), serialized.clone());
+                    }
+                }
+            }
+            signals
+        })
+        .unwrap_or_default();
+
+        batch(|| {
+            for (key, signal, serialized) in signals {
+                restore_value(key, &*signal, serialized);
+            }
+        });
+    }
+}
@@ -78,11 +78,59 @@ where
     cfg_if! {
         if #[cfg(target_arch = "wasm32")] {
             wasm_bindgen_futures::spawn_local(fut)
-        }
-        else if #[cfg(any(test, doctest))] {
-            tokio_test::block_on(fut);
         } else {
-            Handle::current().block_on(fut);
+            block_on_local(Box::pin(fut));
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type LocalFuture = std::pin::Pin<Box<dyn Future<Output = ()>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    /// Futures spawned while another one is being blocked on.
+    static QUEUED: std::cell::RefCell<Option<std::collections::VecDeque<LocalFuture>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Blocks on `fut`, and then on every future spawned while doing so.
+///
+/// A thread that is already blocking on a future can't block on another one,
+/// so a future spawned from inside a future (or from anything it calls, such
+/// as an effect) runs once the outer one has finished.
+#[cfg(not(target_arch = "wasm32"))]
+fn block_on_local(fut: LocalFuture) {
+    struct Reset;
+
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            QUEUED.with(|queued| *queued.borrow_mut() = None);
+        }
+    }
+
+    let fut = QUEUED.with(|queued| match queued.borrow_mut().as_mut() {
+        Some(queued) => {
+            queued.push_back(fut);
+            None
+        }
+        None => Some(fut),
+    });
+    let Some(fut) = fut else {
+        return;
+    };
+
+    QUEUED.with(|queued| *queued.borrow_mut() = Some(Default::default()));
+    let _reset = Reset;
+    let mut next = Some(fut);
+    while let Some(fut) = next {
+        cfg_if! {
+            if #[cfg(any(test, doctest))] {
+                tokio_test::block_on(fut);
+            } else {
+                Handle::current().block_on(fut);
+            }
         }
+        next = QUEUED.with(|queued| queued.borrow_mut().as_mut()?.pop_front());
     }
 }
@@ -0,0 +1,367 @@
+use crate::{
+    store_value, trigger::KeyedTriggers, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWith, SignalWithUntracked, StoredValue,
+};
+use std::{cell::RefCell, fmt, rc::Rc};
+
+/// A reactive tree of state, in which every path is tracked separately.
+///
+/// Reading a path (with [`.get()`](SignalGet::get), [`.with()`](SignalWith::with)
+/// and friends) subscribes to that path only. Writing to a path notifies the
+/// subscribers of that path, of every path inside it, and of every path that
+/// contains it, but never those of its siblings. This means a large app state
+/// can live in one store without every write waking up every reader, which is
+/// what happens with an [`RwSignal`](crate::RwSignal) and
+/// [`create_slice`](crate::create_slice).
+///
+/// Paths are built from the root with [`StoreField::field`] for struct fields,
+/// [`StoreField::index`] for `Vec` items and [`StoreField::some`] for the
+/// contents of an `Option`. Reading or writing a path that does not currently
+/// exist (an index past the end of a `Vec`, or the contents of a `None`) does
+/// nothing: the `try_` methods return `None`, and the others panic.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # let runtime = create_runtime();
+/// #[derive(Default)]
+/// struct User {
+///     name: String,
+///     email: String,
+/// }
+///
+/// #[derive(Default)]
+/// struct State {
+///     user: User,
+///     todos: Vec<String>,
+/// }
+///
+/// let store = create_store(State::default());
+/// let user = store.field("user", |s| &s.user, |s| &mut s.user);
+/// let name = user.field("name", |u| &u.name, |u| &mut u.name);
+/// let email = user.field("email", |u| &u.email, |u| &mut u.email);
+///
+/// let name_runs = Rc::new(Cell::new(0));
+/// create_isomorphic_effect({
+///     let name_runs = Rc::clone(&name_runs);
+///     move |_| {
+///         name.track();
+///         name_runs.set(name_runs.get() + 1);
+///     }
+/// });
+///
+/// // writing to a sibling does not wake up readers of `name`
+/// email.set("bob@example.com".into());
+/// assert_eq!(name_runs.get(), 1);
+///
+/// name.set("Bob".into());
+/// assert_eq!(name_runs.get(), 2);
+/// assert_eq!(user.with(|u| u.name.clone()), "Bob");
+///
+/// let todos = store.field("todos", |s| &s.todos, |s| &mut s.todos);
+/// todos.update(|todos| todos.push("write docs".into()));
+/// assert_eq!(todos.index(0).get(), "write docs");
+/// assert_eq!(todos.index(1).try_get(), None);
+/// # runtime.dispose();
+/// ```
+pub type Store<T> = StoreField<T, T>;
+
+/// Creates a [`Store`] holding the given value.
+#[track_caller]
+pub fn create_store<T>(value: T) -> Store<T>
+where
+    T: 'static,
+{
+    Store::new(value)
+}
+
+/// One path into a [`Store`], with a value of type `U` in a store holding a
+/// `T`. The store itself is the path to its root.
+///
+/// See [`Store`] for details.
+pub struct StoreField<T, U>
+where
+    T: 'static,
+    U: 'static,
+{
+    state: StoredValue<StoreState<T>>,
+    field: StoredValue<FieldState<T, U>>,
+}
+
+/// One segment of the path to a [`StoreField`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PathSegment {
+    Field(&'static str),
+    Index(usize),
+    Some,
+}
+
+struct StoreState<T> {
+    value: RefCell<T>,
+    triggers: KeyedTriggers<Vec<PathSegment>>,
+}
+
+type Getter<T, U> = Rc<dyn Fn(&T) -> Option<&U>>;
+type GetterMut<T, U> = Rc<dyn Fn(&mut T) -> Option<&mut U>>;
+
+struct FieldState<T, U> {
+    path: Vec<PathSegment>,
+    get: Getter<T, U>,
+    get_mut: GetterMut<T, U>,
+}
+
+impl<T> StoreField<T, T> {
+    /// Creates a [`Store`] holding the given value.
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        let state = store_value(StoreState {
+            value: RefCell::new(value),
+            triggers: KeyedTriggers::new(),
+        });
+        let field = store_value(FieldState {
+            path: Vec::new(),
+            get: getter(|value| Some(value)),
+            get_mut: getter_mut(|value| Some(value)),
+        });
+        Self { state, field }
+    }
+}
+
+impl<T, U> StoreField<T, U> {
+    /// Returns the path to one of the fields of this value.
+    ///
+    /// `name` identifies the field within this value, and must be different
+    /// for each of its fields. `get` and `get_mut` must both return the same
+    /// field.
+    #[track_caller]
+    pub fn field<V>(
+        self,
+        name: &'static str,
+        get: impl Fn(&U) -> &V + 'static,
+        get_mut: impl Fn(&mut U) -> &mut V + 'static,
+    ) -> StoreField<T, V> {
+        self.project(
+            PathSegment::Field(name),
+            move |value| Some(get(value)),
+            move |value| Some(get_mut(value)),
+        )
+    }
+
+    #[track_caller]
+    fn project<V>(
+        self,
+        segment: PathSegment,
+        get: impl Fn(&U) -> Option<&V> + 'static,
+        get_mut: impl Fn(&mut U) -> Option<&mut V> + 'static,
+    ) -> StoreField<T, V> {
+        let (mut path, parent_get, parent_get_mut) = self
+            .field
+            .with_value(|field| (field.path.clone(), field.get.clone(), field.get_mut.clone()));
+        path.push(segment);
+
+        let field = store_value(FieldState {
+            path,
+            get: getter(move |value| parent_get(value).and_then(&get)),
+            get_mut: getter_mut(move |value| parent_get_mut(value).and_then(&get_mut)),
+        });
+        StoreField {
+            state: self.state,
+            field,
+        }
+    }
+}
+
+impl<T, U> StoreField<T, Vec<U>> {
+    /// Returns the path to the item at `index`, which only exists while the
+    /// `Vec` is long enough.
+    #[track_caller]
+    pub fn index(self, index: usize) -> StoreField<T, U> {
+        self.project(
+            PathSegment::Index(index),
+            move |items| items.get(index),
+            move |items| items.get_mut(index),
+        )
+    }
+}
+
+impl<T, U> StoreField<T, Option<U>> {
+    /// Returns the path to the contents of the `Option`, which only exists
+    /// while it is `Some`.
+    #[track_caller]
+    pub fn some(self) -> StoreField<T, U> {
+        self.project(PathSegment::Some, Option::as_ref, Option::as_mut)
+    }
+}
+
+impl<T, U> StoreField<T, U> {
+    fn try_with_inner<O>(&self, track: bool, f: impl FnOnce(&U) -> O) -> Option<O> {
+        let (path, get) = self
+            .field
+            .try_with_value(|field| (field.path.clone(), Rc::clone(&field.get)))?;
+        self.state
+            .try_with_value(|state| {
+                if track {
+                    state.triggers.track(path.as_slice());
+                }
+                let value = state.value.borrow();
+                get(&value).map(f)
+            })
+            .flatten()
+    }
+
+    fn try_update_inner<O>(&self, f: impl FnOnce(&mut U) -> O) -> Option<O> {
+        let (path, get_mut) = self
+            .field
+            .try_with_value(|field| (field.path.clone(), Rc::clone(&field.get_mut)))?;
+        self.state
+            .try_with_value(|state| {
+                let result = {
+                    let mut value = state.value.borrow_mut();
+                    get_mut(&mut value).map(f)
+                };
+                // the paths inside this one, and the ones containing it,
+                // may have changed too
+                if result.is_some() {
+                    state
+                        .triggers
+                        .notify_where(|other| other.starts_with(&path) || path.starts_with(other));
+                }
+                result
+            })
+            .flatten()
+    }
+}
+
+fn getter<T, U>(f: impl Fn(&T) -> Option<&U> + 'static) -> Getter<T, U> {
+    Rc::new(f)
+}
+
+fn getter_mut<T, U>(f: impl Fn(&mut T) -> Option<&mut U> + 'static) -> GetterMut<T, U> {
+    Rc::new(f)
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn panic_missing_path() -> ! {
+    panic!(
+        "Tried to access a path in a store that does not exist, or a store \
+         that has been disposed."
+    )
+}
+
+impl<T, U> Clone for StoreField<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U> Copy for StoreField<T, U> {}
+
+impl<T, U> fmt::Debug for StoreField<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("StoreField");
+        s.field("state", &self.state);
+        if let Some(path) = self.field.try_with_value(|field| field.path.clone()) {
+            s.field("path", &path);
+        }
+        s.finish()
+    }
+}
+
+impl<T, U> PartialEq for StoreField<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state && self.field == other.field
+    }
+}
+
+impl<T, U> Eq for StoreField<T, U> {}
+
+impl<T, U> SignalWith for StoreField<T, U> {
+    type Value = U;
+
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&U) -> O) -> O {
+        match self.try_with_inner(true, f) {
+            Some(value) => value,
+            None => panic_missing_path(),
+        }
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(&U) -> O) -> Option<O> {
+        self.try_with_inner(true, f)
+    }
+}
+
+impl<T, U> SignalWithUntracked for StoreField<T, U> {
+    type Value = U;
+
+    #[track_caller]
+    fn with_untracked<O>(&self, f: impl FnOnce(&U) -> O) -> O {
+        match self.try_with_inner(false, f) {
+            Some(value) => value,
+            None => panic_missing_path(),
+        }
+    }
+
+    #[track_caller]
+    fn try_with_untracked<O>(&self, f: impl FnOnce(&U) -> O) -> Option<O> {
+        self.try_with_inner(false, f)
+    }
+}
+
+impl<T, U: Clone> SignalGet for StoreField<T, U> {
+    type Value = U;
+
+    #[track_caller]
+    fn get(&self) -> U {
+        self.with(U::clone)
+    }
+
+    fn try_get(&self) -> Option<U> {
+        self.try_with(U::clone)
+    }
+}
+
+impl<T, U: Clone> SignalGetUntracked for StoreField<T, U> {
+    type Value = U;
+
+    #[track_caller]
+    fn get_untracked(&self) -> U {
+        self.with_untracked(U::clone)
+    }
+
+    fn try_get_untracked(&self) -> Option<U> {
+        self.try_with_untracked(U::clone)
+    }
+}
+
+impl<T, U> SignalUpdate for StoreField<T, U> {
+    type Value = U;
+
+    #[track_caller]
+    fn update(&self, f: impl FnOnce(&mut U)) {
+        if self.try_update_inner(f).is_none() {
+            panic_missing_path()
+        }
+    }
+
+    fn try_update<O>(&self, f: impl FnOnce(&mut U) -> O) -> Option<O> {
+        self.try_update_inner(f)
+    }
+}
+
+impl<T, U> SignalSet for StoreField<T, U> {
+    type Value = U;
+
+    #[track_caller]
+    fn set(&self, new_value: U) {
+        self.update(|value| *value = new_value);
+    }
+
+    fn try_set(&self, new_value: U) -> Option<U> {
+        let mut new_value = Some(new_value);
+        self.try_update_inner(|value| *value = new_value.take().unwrap());
+        new_value
+    }
+}
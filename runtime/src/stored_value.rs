@@ -1,4 +1,4 @@
-use crate::{with_runtime, Runtime, ScopeProperty};
+use crate::{with_runtime, ReactiveSystemError, Runtime, ScopeProperty, ValueKind};
 use std::{
     cell::RefCell,
     fmt,
@@ -27,6 +27,8 @@ where
 {
     id: StoredValueId,
     ty: PhantomData<T>,
+    #[cfg(any(debug_assertions, feature = "ssr"))]
+    defined_at: &'static std::panic::Location<'static>,
 }
 
 impl<T: Default> Default for StoredValue<T> {
@@ -95,7 +97,10 @@ impl<T> StoredValue<T> {
     where
         T: Clone,
     {
-        self.try_get_value().expect("could not get stored value")
+        match self.try_get_value() {
+            Some(value) => value,
+            None => self.panic_disposed(),
+        }
     }
 
     /// Same as [`StoredValue::get_value`] but will not panic by default.
@@ -130,7 +135,10 @@ impl<T> StoredValue<T> {
     //               track the stored value. This method will also be removed in \
     //               a future version of `leptos`"]
     pub fn with_value<U>(&self, f: impl FnOnce(&T) -> U) -> U {
-        self.try_with_value(f).expect("could not get stored value")
+        match self.try_with_value(f) {
+            Some(value) => value,
+            None => self.panic_disposed(),
+        }
     }
 
     /// Same as [`StoredValue::with_value`] but returns [`Some(O)]` only if
@@ -189,8 +197,9 @@ impl<T> StoredValue<T> {
     /// stored value has been disposed.
     #[track_caller]
     pub fn update_value(&self, f: impl FnOnce(&mut T)) {
-        self.try_update_value(f)
-            .expect("could not set stored value");
+        if self.try_update_value(f).is_none() {
+            self.panic_disposed()
+        }
     }
 
     /// Same as [`Self::update_value`], but returns [`Some(O)`] if the
@@ -213,9 +222,42 @@ impl<T> StoredValue<T> {
     pub fn dispose(self) {
         _ = with_runtime(|runtime| {
             runtime.stored_values.borrow_mut().remove(self.id);
+            #[cfg(debug_assertions)]
+            runtime.stored_value_locations.borrow_mut().remove(self.id);
         });
     }
 
+    /// Returns [`ReactiveSystemError::Disposed`], naming where the value was
+    /// created, if it has already been disposed.
+    #[track_caller]
+    pub fn check_alive(&self) -> Result<(), ReactiveSystemError> {
+        if with_runtime(|runtime| runtime.stored_values.borrow().contains_key(self.id))
+            .unwrap_or(false)
+        {
+            Ok(())
+        } else {
+            Err(ReactiveSystemError::disposed(
+                ValueKind::StoredValue,
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            ))
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn panic_disposed(&self) -> ! {
+        panic!(
+            "{}",
+            ReactiveSystemError::disposed(
+                ValueKind::StoredValue,
+                #[cfg(any(debug_assertions, feature = "ssr"))]
+                self.defined_at,
+            )
+        )
+    }
+
     /// Sets the stored value.
     ///
     /// # Examples
@@ -306,18 +348,26 @@ pub fn store_value<T>(value: T) -> StoredValue<T>
 where
     T: 'static,
 {
+    #[cfg(any(debug_assertions, feature = "ssr"))]
+    let defined_at = std::panic::Location::caller();
     let id = with_runtime(|runtime| {
         let id = runtime
             .stored_values
             .borrow_mut()
             .insert(Rc::new(RefCell::new(value)));
-        runtime.push_scope_property(ScopeProperty::StoredValue(id));
+        runtime.register_property(
+            ScopeProperty::StoredValue(id),
+            #[cfg(debug_assertions)]
+            defined_at,
+        );
         id
     })
     .expect("store_value failed to find the current runtime");
     StoredValue {
         id,
         ty: PhantomData,
+        #[cfg(any(debug_assertions, feature = "ssr"))]
+        defined_at,
     }
 }
 
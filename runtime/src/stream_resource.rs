@@ -0,0 +1,247 @@
+use crate::{
+    async_memo::Untracked, batch, create_isomorphic_effect, create_memo, create_rw_signal,
+    on_cleanup, spawn::spawn_local, store_value, suspense::Suspended, untrack, RwSignal, Signal,
+    SignalGet, SignalGetUntracked, SignalSet, SignalWith, SignalWithUntracked, StoredValue,
+};
+use futures::{
+    future::{abortable, AbortHandle},
+    Stream, StreamExt,
+};
+use std::{cell::RefCell, rc::Rc};
+
+/// Creates a [`StreamResource`], which follows a [`Stream`] and holds the
+/// latest item it yielded.
+///
+/// Whenever `source` changes, the current stream is dropped, and a new one
+/// is made for the new source with `stream_fn`. Unlike
+/// [`create_signal_from_stream`](crate::create_signal_from_stream), the
+/// resource also tells whether the stream is [loading](StreamResource::loading)
+/// its first item or has [finished](StreamResource::finished).
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// # let tokio = tokio::runtime::Runtime::new().unwrap();
+/// # let _guard = tokio.enter();
+/// let (count, set_count) = create_signal(3);
+/// let numbers = create_stream_resource(
+///     move || count.get(),
+///     |count| futures::stream::iter(1..=count),
+/// );
+/// assert_eq!(numbers.get(), Some(3));
+/// assert!(numbers.finished().get());
+///
+/// set_count.set(5);
+/// assert_eq!(numbers.get(), Some(5));
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn create_stream_resource<S, T, St>(
+    source: impl Fn() -> S + 'static,
+    stream_fn: impl Fn(S) -> St + 'static,
+) -> StreamResource<T>
+where
+    S: PartialEq + Clone + 'static,
+    T: 'static,
+    St: Stream<Item = T> + 'static,
+{
+    let source = create_memo(move |_| source());
+    let state = StreamResourceState {
+        value: create_rw_signal(None),
+        loading: create_rw_signal(false),
+        finished: create_rw_signal(false),
+        suspense: Default::default(),
+        in_flight: Default::default(),
+    };
+
+    on_cleanup({
+        let state = state.clone();
+        move || {
+            state.abort();
+            // nothing will ever load, so don't keep anything suspended
+            state.suspense.ready();
+        }
+    });
+
+    create_isomorphic_effect({
+        let state = state.clone();
+        move |_| {
+            let source = source.get();
+            let stream = untrack(|| stream_fn(source));
+            state.restart(stream);
+        }
+    });
+
+    StreamResource {
+        state: store_value(state),
+    }
+}
+
+/// The latest item of a [`Stream`] that is restarted whenever its source
+/// changes, created with [`create_stream_resource`].
+///
+/// Reading the resource under a [`SuspenseContext`](crate::SuspenseContext)
+/// keeps the context pending until the current stream yields its first item (or ends without
+/// yielding one).
+pub struct StreamResource<T: 'static> {
+    state: StoredValue<StreamResourceState<T>>,
+}
+
+struct StreamResourceState<T: 'static> {
+    value: RwSignal<Option<T>>,
+    loading: RwSignal<bool>,
+    finished: RwSignal<bool>,
+    suspense: Rc<Suspended>,
+    in_flight: Rc<RefCell<Option<AbortHandle>>>,
+}
+
+impl<T> Clone for StreamResourceState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value,
+            loading: self.loading,
+            finished: self.finished,
+            suspense: Rc::clone(&self.suspense),
+            in_flight: Rc::clone(&self.in_flight),
+        }
+    }
+}
+
+impl<T> StreamResourceState<T> {
+    fn abort(&self) {
+        if let Some(handle) = self.in_flight.take() {
+            handle.abort();
+        }
+    }
+
+    fn restart(&self, stream: impl Stream<Item = T> + 'static) {
+        self.abort();
+        batch(|| {
+            self.loading.set(true);
+            self.finished.set(false);
+        });
+        self.suspense.wait();
+
+        let this = self.clone();
+        // polled outside of the effect that restarts the stream, which would
+        // otherwise subscribe to whatever the stream reads
+        let (fut, handle) = abortable(Untracked(Box::pin(async move {
+            let mut stream = Box::pin(stream);
+            while let Some(item) = stream.next().await {
+                // readers run again once the batch is done, and shouldn't
+                // find the resource still waiting
+                batch(|| {
+                    this.suspense.ready();
+                    this.value.try_set(Some(item));
+                    this.loading.try_set(false);
+                });
+            }
+            this.in_flight.take();
+            batch(|| {
+                this.suspense.ready();
+                this.loading.try_set(false);
+                this.finished.try_set(true);
+            });
+        })));
+        *self.in_flight.borrow_mut() = Some(handle);
+        // an aborted stream leaves everything to the one that replaced it
+        spawn_local(async move {
+            _ = fut.await;
+        });
+    }
+}
+
+impl<T> StreamResource<T> {
+    /// Whether the current stream has yet to yield its first item.
+    pub fn loading(&self) -> Signal<bool> {
+        self.state.with_value(|state| state.loading).into()
+    }
+
+    /// Whether the current stream has ended.
+    pub fn finished(&self) -> Signal<bool> {
+        self.state.with_value(|state| state.finished).into()
+    }
+
+    fn try_with_inner<O>(&self, track: bool, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        let state = self.state.try_with_value(StreamResourceState::clone)?;
+        if track {
+            state.suspense.track();
+            state.value.try_with(f)
+        } else {
+            state.value.try_with_untracked(f)
+        }
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn panic_disposed() -> ! {
+    panic!("Tried to access a stream resource that has been disposed.")
+}
+
+impl<T> Clone for StreamResource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StreamResource<T> {}
+
+impl<T> SignalWith for StreamResource<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match self.try_with_inner(true, f) {
+            Some(value) => value,
+            None => panic_disposed(),
+        }
+    }
+
+    fn try_with<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.try_with_inner(true, f)
+    }
+}
+
+impl<T> SignalWithUntracked for StreamResource<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> O {
+        match self.try_with_inner(false, f) {
+            Some(value) => value,
+            None => panic_disposed(),
+        }
+    }
+
+    fn try_with_untracked<O>(&self, f: impl FnOnce(&Option<T>) -> O) -> Option<O> {
+        self.try_with_inner(false, f)
+    }
+}
+
+impl<T: Clone> SignalGet for StreamResource<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get(&self) -> Option<T> {
+        self.with(Option::clone)
+    }
+
+    fn try_get(&self) -> Option<Option<T>> {
+        self.try_with(Option::clone)
+    }
+}
+
+impl<T: Clone> SignalGetUntracked for StreamResource<T> {
+    type Value = Option<T>;
+
+    #[track_caller]
+    fn get_untracked(&self) -> Option<T> {
+        self.with_untracked(Option::clone)
+    }
+
+    fn try_get_untracked(&self) -> Option<Option<T>> {
+        self.try_with_untracked(Option::clone)
+    }
+}
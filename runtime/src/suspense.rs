@@ -1,12 +1,17 @@
 //! Types that handle asynchronous data loading via `<Suspense/>`.
 
 use crate::{
-    create_isomorphic_effect, create_memo, create_rw_signal, create_signal, oco::Oco,
-    queue_microtask, signal::SignalGet, store_value, Memo, ReadSignal, RwSignal, SignalSet,
-    SignalUpdate, StoredValue, WriteSignal,
+    create_isomorphic_effect, create_memo, create_rw_signal, create_signal, on_cleanup,
+    provide_context, queue_microtask, signal::SignalGet, store_value, use_context, Memo,
+    ReadSignal, RwSignal, Signal, SignalGetUntracked, SignalSet, SignalUpdate, StoredValue,
+    WriteSignal,
 };
 use futures::Future;
-use std::{cell::RefCell, collections::VecDeque, pin::Pin, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    rc::Rc,
+};
 
 /// Tracks [`Resource`](crate::Resource)s that are read under a suspense context,
 /// i.e., within a [`Suspense`](https://docs.rs/leptos_core/latest/leptos_core/fn.Suspense.html) component.
@@ -193,24 +198,171 @@ impl Default for SuspenseContext {
     }
 }
 
-/// Represents a chunk in a stream of HTML.
-pub enum StreamChunk {
-    /// A chunk of synchronous HTML.
-    Sync(Oco<'static, str>),
-    /// A future that resolves to be a list of additional chunks.
-    Async {
-        /// The HTML chunks this contains.
-        chunks: Pin<Box<dyn Future<Output = VecDeque<StreamChunk>>>>,
-        /// Whether this should block the stream.
-        should_block: bool,
-    },
+/// The suspense contexts an async value has been read under, and whether it
+/// is holding them back. Used by values that aren't [`Resource`](crate::Resource)s,
+/// such as stream resources and queries.
+///
+/// Notifying a context runs its effects, which may read the value again, so
+/// nothing is borrowed while doing so.
+#[derive(Default)]
+pub(crate) struct Suspended {
+    contexts: RefCell<HashSet<SuspenseContext>>,
+    waiting: Cell<bool>,
+}
+
+impl Suspended {
+    /// Registers the suspense context the value is being read under, if any,
+    /// until the current owner is cleaned up. Readers register again when
+    /// they are run again.
+    pub fn track(self: &Rc<Self>) {
+        if let Some(context) = use_context::<SuspenseContext>() {
+            self.register(context);
+            let this = Rc::clone(self);
+            on_cleanup(move || this.unregister(&context));
+        }
+    }
+
+    fn register(&self, context: SuspenseContext) {
+        let inserted = self.contexts.borrow_mut().insert(context);
+        if inserted && self.waiting.get() {
+            context.increment(false);
+        }
+    }
+
+    fn unregister(&self, context: &SuspenseContext) {
+        let removed = self.contexts.borrow_mut().remove(context);
+        if removed && self.waiting.get() {
+            context.decrement(false);
+        }
+    }
+
+    pub fn wait(&self) {
+        if !self.waiting.replace(true) {
+            let contexts = self.contexts.borrow().clone();
+            for context in contexts {
+                context.increment(false);
+            }
+        }
+    }
+
+    pub fn ready(&self) {
+        if self.waiting.replace(false) {
+            let contexts = self.contexts.borrow().clone();
+            for context in contexts {
+                context.decrement(false);
+            }
+        }
+    }
 }
 
-impl std::fmt::Debug for StreamChunk {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            StreamChunk::Sync(data) => write!(f, "StreamChunk::Sync({data:?})"),
-            StreamChunk::Async { .. } => write!(f, "StreamChunk::Async(_)"),
+/// Creates a [`Transition`], and provides it and its [`SuspenseContext`] to
+/// the current owner and its children.
+///
+/// Resources read under the transition count toward its suspense context, so
+/// a state change made with [`start_transition`] is held back until they
+/// have all loaded.
+pub fn create_transition() -> Transition {
+    let transition = Transition {
+        suspense: SuspenseContext::new(),
+        pending: create_rw_signal(false),
+    };
+    create_isomorphic_effect(move |_| {
+        if transition.suspense.pending_resources.get() == 0 {
+            transition.finish();
         }
+    });
+    provide_context(transition.suspense);
+    provide_context(transition);
+    transition
+}
+
+/// Runs `f` as part of the nearest [`Transition`] provided in the current
+/// context, or just runs it if there is none.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # let runtime = create_runtime();
+/// let transition = create_transition();
+/// let (tab, set_tab) = create_signal("home");
+/// let shown = transition.hold(move || tab.get());
+/// assert_eq!(shown.get(), "home");
+///
+/// start_transition(|| {
+///     set_tab.set("settings");
+///     // pretend the settings tab started loading a resource
+///     transition.suspense().increment(false);
+/// });
+/// assert!(transition.is_pending().get());
+/// assert_eq!(shown.get(), "home");
+///
+/// transition.suspense().decrement(false);
+/// assert!(!transition.is_pending().get());
+/// assert_eq!(shown.get(), "settings");
+/// # runtime.dispose();
+/// ```
+pub fn start_transition(f: impl FnOnce()) {
+    match use_context::<Transition>() {
+        Some(transition) => transition.start(f),
+        None => f(),
+    }
+}
+
+/// Holds values on their previous state while the resources read under a
+/// [`SuspenseContext`] reload, so that switching between views shows the old
+/// one until the new one is ready, rather than an empty one. Created with
+/// [`create_transition`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Transition {
+    suspense: SuspenseContext,
+    pending: RwSignal<bool>,
+}
+
+impl Transition {
+    /// Runs `f`, and then holds every value made with
+    /// [`hold`](Transition::hold) until the resources read under the
+    /// transition have loaded, when they are all updated at once.
+    pub fn start(&self, f: impl FnOnce()) {
+        self.pending.set(true);
+        f();
+        // nothing started loading, so there is nothing to wait for
+        if self.suspense.pending_resources.get_untracked() == 0 {
+            self.finish();
+        }
+    }
+
+    fn finish(&self) {
+        if self.pending.get_untracked() {
+            self.pending.set(false);
+        }
+    }
+
+    /// Whether a transition is waiting for resources to load.
+    pub fn is_pending(&self) -> Signal<bool> {
+        self.pending.read_only().into()
+    }
+
+    /// The suspense context resources read under the transition count
+    /// toward.
+    pub fn suspense(&self) -> SuspenseContext {
+        self.suspense
+    }
+
+    /// Creates a memo of `f` that keeps its previous value while the
+    /// transition is pending.
+    ///
+    /// `f` still runs during the transition, so that it keeps reading (and
+    /// waiting for) the same resources.
+    pub fn hold<T>(&self, f: impl Fn() -> T + 'static) -> Memo<T>
+    where
+        T: Clone + PartialEq + 'static,
+    {
+        let pending = self.pending;
+        create_memo(move |previous: Option<&T>| {
+            let next = f();
+            match previous {
+                Some(previous) if pending.get() => previous.clone(),
+                _ => next,
+            }
+        })
     }
 }
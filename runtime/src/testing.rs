@@ -0,0 +1,148 @@
+//! Utilities for testing reactive code.
+//!
+//! [`run_test`] runs a test in a runtime of its own, with a [`TestClock`] so
+//! that anything using timers can be tested without sleeping, and a Tokio
+//! runtime so that futures can be spawned and driven to completion.
+//!
+//! ```
+//! use goober_runtime::{testing::run_test, *};
+//! use std::time::Duration;
+//!
+//! run_test(|cx| {
+//!     let (query, set_query) = create_signal(String::new());
+//!     let search = debounced(query, Duration::from_millis(300));
+//!     let searches = cx.count_runs(move || {
+//!         search.track();
+//!     });
+//!
+//!     set_query.set("goo".to_string());
+//!     set_query.set("goober".to_string());
+//!     cx.advance(Duration::from_millis(300));
+//!     assert_eq!(search.get(), "goober");
+//!     searches.assert_runs(2);
+//! });
+//! ```
+
+use crate::{create_isomorphic_effect, create_runtime, runtime::RuntimeId, EffectPhase, TestClock};
+use std::{cell::Cell, future::Future, rc::Rc, time::Duration};
+
+/// Runs `f` on a new thread, with a reactive runtime of its own, and returns
+/// what it returned. If `f` panics, so does this, with the same payload.
+///
+/// The runtime uses a [`TestClock`], which only moves with
+/// [`TestContext::advance`]. A Tokio runtime is entered for the duration of
+/// `f`, so that [`spawn_local`](crate::spawn_local) and anything built on it
+/// (such as resources) can run futures.
+pub fn run_test<T>(f: impl FnOnce(&TestContext) -> T + Send) -> T
+where
+    T: Send,
+{
+    std::thread::scope(|scope| {
+        let test = scope.spawn(|| {
+            // futures are blocked on through a handle, which can only drive
+            // timers and IO if the runtime has worker threads of its own
+            let tokio = tokio::runtime::Runtime::new()
+                .expect("could not build the Tokio runtime for the test");
+            let _guard = tokio.enter();
+            let runtime = create_runtime();
+            let cx = TestContext {
+                runtime,
+                clock: TestClock::install(),
+                tokio: &tokio,
+            };
+            let value = f(&cx);
+            runtime.dispose();
+            value
+        });
+        match test.join() {
+            Ok(value) => value,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    })
+}
+
+/// The runtime a test started with [`run_test`] runs in.
+pub struct TestContext<'a> {
+    runtime: RuntimeId,
+    clock: TestClock,
+    tokio: &'a tokio::runtime::Runtime,
+}
+
+impl TestContext<'_> {
+    /// The reactive runtime of the test.
+    pub fn runtime(&self) -> RuntimeId {
+        self.runtime
+    }
+
+    /// The clock of the test's runtime.
+    pub fn clock(&self) -> &TestClock {
+        &self.clock
+    }
+
+    /// Runs every timer that is due, applies every write queued by a
+    /// [`SendSignal`](crate::SendSignal), and runs every effect that is still
+    /// waiting to run, such as those of a [`batch`](crate::batch) that
+    /// panicked, or of a [deferred](RuntimeId::set_phase_deferred) phase.
+    pub fn flush(&self) {
+        self.runtime.run_timers();
+        self.runtime.run_sync_writes();
+        self.runtime.run_phase(EffectPhase::PostRender);
+    }
+
+    /// Moves the clock forward by `by`, running every timer that comes due,
+    /// and then [flushes](TestContext::flush).
+    pub fn advance(&self, by: Duration) {
+        self.clock.advance(by);
+        self.flush();
+    }
+
+    /// Drives `fut` to completion, and then [flushes](TestContext::flush).
+    pub fn block_on<F: Future>(&self, fut: F) -> F::Output {
+        let output = self.tokio.block_on(fut);
+        self.flush();
+        output
+    }
+
+    /// Creates an effect that runs `f`, and counts how many times it has
+    /// run.
+    pub fn count_runs(&self, f: impl Fn() + 'static) -> RunCounter {
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect({
+            let runs = Rc::clone(&runs);
+            move |_| {
+                f();
+                runs.set(runs.get() + 1);
+            }
+        });
+        RunCounter { runs }
+    }
+}
+
+/// Counts the runs of an effect created with [`TestContext::count_runs`].
+#[derive(Debug, Clone)]
+pub struct RunCounter {
+    runs: Rc<Cell<usize>>,
+}
+
+impl RunCounter {
+    /// How many times the effect has run, including its first run.
+    pub fn runs(&self) -> usize {
+        self.runs.get()
+    }
+
+    /// Asserts that the effect has run exactly `expected` times, including
+    /// its first run.
+    #[track_caller]
+    pub fn assert_runs(&self, expected: usize) {
+        let runs = self.runs();
+        assert_eq!(
+            runs, expected,
+            "expected the effect to have run {expected} times, but it ran {runs} times"
+        );
+    }
+
+    /// Starts counting from zero again.
+    pub fn reset(&self) {
+        self.runs.set(0);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::{
+    create_isomorphic_effect, create_signal, on_cleanup,
+    runtime::{with_runtime, Runtime, RuntimeId},
+    ReadSignal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, WriteSignal,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Callbacks that should run once some time has passed, such as the ticks of
+/// an interval or the next refetch of a polling [`Resource`](crate::Resource).
+///
+/// Futures can't be used for this outside the browser, where
+/// [`spawn_local`](crate::spawn_local) blocks until the future is done, so
+/// the host (such as the runner's event loop) runs the timers instead, with
+/// [`RuntimeId::run_timers`].
+#[derive(Default)]
+pub(crate) struct Timers {
+    next_id: u64,
+    queue: BTreeMap<TimerHandle, Box<dyn FnOnce()>>,
+    /// The time of a [`TestClock`], if one is installed.
+    clock: Option<Rc<Cell<Instant>>>,
+}
+
+/// A callback scheduled with [`set_timeout_with_handle`], which can be
+/// cancelled until it has run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerHandle {
+    deadline: Instant,
+    // keeps timers with the same deadline in the order they were set
+    id: u64,
+}
+
+/// The current time, as told by the runtime's clock.
+pub(crate) fn clock_now() -> Instant {
+    with_runtime(|runtime| {
+        runtime
+            .timers
+            .borrow()
+            .clock
+            .as_ref()
+            .map(|clock| clock.get())
+    })
+    .ok()
+    .flatten()
+    .unwrap_or_else(Instant::now)
+}
+
+/// Runs `f` once `delay` has passed, the next time the timers are run.
+pub(crate) fn set_timer(delay: Duration, f: impl FnOnce() + 'static) -> Option<TimerHandle> {
+    set_timer_at(clock_now() + delay, f)
+}
+
+fn set_timer_at(deadline: Instant, f: impl FnOnce() + 'static) -> Option<TimerHandle> {
+    with_runtime(|runtime| {
+        let mut timers = runtime.timers.borrow_mut();
+        let handle = TimerHandle {
+            deadline,
+            id: timers.next_id,
+        };
+        timers.next_id += 1;
+        timers.queue.insert(handle, Box::new(f));
+        handle
+    })
+    .ok()
+}
+
+impl TimerHandle {
+    /// Stops the callback from running, if it hasn't yet.
+    pub fn cancel(self) {
+        _ = with_runtime(|runtime| runtime.timers.borrow_mut().queue.remove(&self));
+    }
+}
+
+impl RuntimeId {
+    /// Runs every timer that is due, and returns when the next one will be.
+    ///
+    /// Timers are used by everything in the runtime that needs to do
+    /// something later, such as [`set_timeout`], [`create_interval`] or
+    /// [polling resources](crate::ResourceOptions::refetch_interval).
+    /// Whatever drives the runtime should call this whenever the time it
+    /// returned has come, and after handling any event, since that may have
+    /// set new timers.
+    pub fn run_timers(self) -> Option<Instant> {
+        let now = clock_now();
+        // timers set while running these wait for the next call, even if
+        // they are already due
+        let last = with_runtime(|runtime| runtime.timers.borrow().next_id).ok()?;
+        loop {
+            let due = with_runtime(|runtime| {
+                let mut timers = runtime.timers.borrow_mut();
+                let handle = *timers
+                    .queue
+                    .keys()
+                    .take_while(|handle| handle.deadline <= now)
+                    .find(|handle| handle.id < last)?;
+                timers.queue.remove(&handle)
+            })
+            .ok()
+            .flatten();
+            match due {
+                Some(f) => f(),
+                None => break,
+            }
+        }
+        self.next_timer()
+    }
+
+    /// Returns when the next timer will be due, if there is one.
+    pub fn next_timer(self) -> Option<Instant> {
+        with_runtime(|runtime| {
+            runtime
+                .timers
+                .borrow()
+                .queue
+                .keys()
+                .next()
+                .map(|handle| handle.deadline)
+        })
+        .ok()
+        .flatten()
+    }
+}
+
+/// A clock that only moves when told to, so that tests of anything that
+/// uses timers run instantly and always the same way.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::time::Duration;
+/// # let runtime = create_runtime();
+/// let clock = TestClock::install();
+/// let ticks = create_interval(Duration::from_secs(1));
+///
+/// clock.advance(Duration::from_millis(3500));
+/// assert_eq!(ticks.get(), 3);
+/// # runtime.dispose();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TestClock {
+    runtime: RuntimeId,
+    now: Rc<Cell<Instant>>,
+}
+
+impl TestClock {
+    /// Replaces the clock of the current runtime with a test clock, starting
+    /// at the current time.
+    pub fn install() -> Self {
+        let now = Rc::new(Cell::new(clock_now()));
+        _ = with_runtime(|runtime| runtime.timers.borrow_mut().clock = Some(Rc::clone(&now)));
+        Self {
+            runtime: Runtime::current(),
+            now,
+        }
+    }
+
+    /// The current time of the clock.
+    pub fn now(&self) -> Instant {
+        self.now.get()
+    }
+
+    /// Moves the clock forward by `by`, running every timer that comes due
+    /// on the way, at the time it was due.
+    pub fn advance(&self, by: Duration) {
+        let target = self.now.get() + by;
+        while let Some(deadline) = self.runtime.next_timer() {
+            if deadline > target {
+                break;
+            }
+            self.now.set(deadline.max(self.now.get()));
+            self.runtime.run_timers();
+        }
+        self.now.set(target);
+        self.runtime.run_timers();
+    }
+}
+
+/// Runs `f` once `delay` has passed, unless the current owner is cleaned up
+/// first.
+#[track_caller]
+pub fn set_timeout(f: impl FnOnce() + 'static, delay: Duration) {
+    _ = set_timeout_with_handle(f, delay);
+}
+
+/// Runs `f` once `delay` has passed, unless the current owner is cleaned up
+/// or the returned handle is cancelled first.
+///
+/// Returns `None` if the runtime has been disposed.
+#[track_caller]
+pub fn set_timeout_with_handle(f: impl FnOnce() + 'static, delay: Duration) -> Option<TimerHandle> {
+    let handle = set_timer(delay, f)?;
+    on_cleanup(move || handle.cancel());
+    Some(handle)
+}
+
+/// A timer that keeps being replaced, and is cancelled along with its owner.
+#[derive(Clone, Default)]
+struct OwnedTimer(Rc<Cell<Option<TimerHandle>>>);
+
+impl OwnedTimer {
+    fn new() -> Self {
+        let timer = Self::default();
+        on_cleanup({
+            let timer = timer.clone();
+            move || timer.cancel()
+        });
+        timer
+    }
+
+    fn set_at(&self, deadline: Instant, f: impl FnOnce() + 'static) {
+        self.cancel();
+        self.0.set(set_timer_at(deadline, f));
+    }
+
+    fn cancel(&self) {
+        if let Some(handle) = self.0.take() {
+            handle.cancel();
+        }
+    }
+
+    fn is_set(&self) -> bool {
+        self.0.get().is_some()
+    }
+}
+
+/// Creates a signal that counts how many times `period` has passed since it
+/// was created, until its owner is cleaned up.
+///
+/// Ticks are scheduled from when the previous one was due, rather than from
+/// when it actually ran, so the interval doesn't drift.
+///
+/// # Panics
+/// Panics if `period` is zero.
+#[track_caller]
+pub fn create_interval(period: Duration) -> ReadSignal<u64> {
+    assert!(!period.is_zero(), "create_interval needs a nonzero period");
+    let (ticks, set_ticks) = create_signal(0);
+    let timer = OwnedTimer::new();
+    schedule_tick(timer, clock_now() + period, period, move || {
+        set_ticks.update(|ticks| *ticks += 1)
+    });
+    ticks
+}
+
+fn schedule_tick(
+    timer: OwnedTimer,
+    deadline: Instant,
+    period: Duration,
+    tick: impl Fn() + 'static,
+) {
+    timer.clone().set_at(deadline, move || {
+        tick();
+        schedule_tick(timer, deadline + period, period, tick);
+    });
+}
+
+/// Creates a signal with the current time, as told by the runtime's clock,
+/// that is updated every `resolution` until its owner is cleaned up.
+///
+/// # Panics
+/// Panics if `resolution` is zero.
+#[track_caller]
+pub fn now(resolution: Duration) -> ReadSignal<Instant> {
+    assert!(!resolution.is_zero(), "now needs a nonzero resolution");
+    let start = clock_now();
+    let (now, set_now) = create_signal(start);
+    schedule_tick(
+        OwnedTimer::new(),
+        start + resolution,
+        resolution,
+        move || set_now.set(clock_now()),
+    );
+    now
+}
+
+/// Creates a signal that follows `signal`, but only once it has stopped
+/// changing for `delay`.
+///
+/// ```
+/// # use goober_runtime::*;
+/// # use std::time::Duration;
+/// # let runtime = create_runtime();
+/// let clock = TestClock::install();
+/// let (query, set_query) = create_signal(String::new());
+/// let search = debounced(query, Duration::from_millis(300));
+///
+/// set_query.set("gob".to_string());
+/// clock.advance(Duration::from_millis(200));
+/// set_query.set("goober".to_string());
+/// clock.advance(Duration::from_millis(200));
+/// assert_eq!(search.get(), "");
+///
+/// clock.advance(Duration::from_millis(100));
+/// assert_eq!(search.get(), "goober");
+/// # runtime.dispose();
+/// ```
+#[track_caller]
+pub fn debounced<T>(
+    signal: impl SignalGet<Value = T> + SignalGetUntracked<Value = T> + 'static,
+    delay: Duration,
+) -> ReadSignal<T>
+where
+    T: Clone + 'static,
+{
+    let (value, set_value) = create_signal(signal.get_untracked());
+    let timer = OwnedTimer::new();
+    create_isomorphic_effect(move |prev: Option<()>| {
+        let new = signal.get();
+        if prev.is_some() {
+            timer.set_at(clock_now() + delay, move || set_value.set(new));
+        }
+    });
+    value
+}
+
+/// Creates a signal that follows `signal`, but changes at most once every
+/// `interval`.
+///
+/// The first change is passed on right away. Changes made during the
+/// following `interval` are held back, and the latest of them is passed on
+/// once it is over.
+#[track_caller]
+pub fn throttled<T>(
+    signal: impl SignalGet<Value = T> + SignalGetUntracked<Value = T> + 'static,
+    interval: Duration,
+) -> ReadSignal<T>
+where
+    T: Clone + 'static,
+{
+    let (value, set_value) = create_signal(signal.get_untracked());
+    let timer = OwnedTimer::new();
+    let held = Rc::new(RefCell::new(None::<T>));
+    create_isomorphic_effect(move |prev: Option<()>| {
+        let new = signal.get();
+        if prev.is_none() {
+            return;
+        }
+        if timer.is_set() {
+            *held.borrow_mut() = Some(new);
+        } else {
+            set_value.set(new);
+            start_throttle(
+                timer.clone(),
+                clock_now() + interval,
+                interval,
+                Rc::clone(&held),
+                set_value,
+            );
+        }
+    });
+    value
+}
+
+/// Ends the window a throttled signal is held in, passing on what was held
+/// back and starting a new window if there was anything.
+fn start_throttle<T: 'static>(
+    timer: OwnedTimer,
+    deadline: Instant,
+    interval: Duration,
+    held: Rc<RefCell<Option<T>>>,
+    set_value: WriteSignal<T>,
+) {
+    timer.clone().set_at(deadline, move || {
+        timer.0.set(None);
+        let next = held.borrow_mut().take();
+        if let Some(next) = next {
+            set_value.set(next);
+            start_throttle(timer, deadline + interval, interval, held, set_value);
+        }
+    });
+}
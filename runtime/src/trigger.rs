@@ -1,10 +1,12 @@
 use crate::{
-    diagnostics,
+    batch, diagnostics,
     diagnostics::*,
     node::NodeId,
-    runtime::{with_runtime, Runtime},
-    SignalGet, SignalSet, SignalUpdate,
+    runtime::{with_runtime, Runtime, ScopeProperty},
+    with_owner, Owner, SignalGet, SignalSet, SignalUpdate,
 };
+use rustc_hash::FxHashMap;
+use std::{borrow::Borrow, cell::RefCell, hash::Hash};
 
 /// Reactive Trigger, notifies reactive code to rerun.
 ///
@@ -295,3 +297,82 @@ impl Fn<()> for Trigger {
         self.track()
     }
 }
+
+/// A set of [`Trigger`]s created on demand for each key that is read, all
+/// owned by the owner that was current when the set was created. Used by
+/// values that track parts of themselves separately, like stores and
+/// reactive collections.
+pub(crate) struct KeyedTriggers<K> {
+    triggers: RefCell<FxHashMap<K, Trigger>>,
+    owner: Option<Owner>,
+}
+
+impl<K: Hash + Eq> KeyedTriggers<K> {
+    pub fn new() -> Self {
+        Self {
+            triggers: Default::default(),
+            owner: Owner::current(),
+        }
+    }
+
+    /// Subscribes the running effect to the given key.
+    pub fn track<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let has_observer =
+            with_runtime(|runtime| runtime.observer.get().is_some()).unwrap_or(false);
+        if !has_observer {
+            return;
+        }
+
+        let trigger = self.triggers.borrow().get(key).copied();
+        let trigger = trigger.unwrap_or_else(|| {
+            // the triggers live as long as their owner, not as long as the
+            // effect that happened to read the key first
+            let trigger = match self.owner {
+                Some(owner) => with_owner(owner, create_trigger),
+                None => create_trigger(),
+            };
+            self.triggers.borrow_mut().insert(key.to_owned(), trigger);
+            trigger
+        });
+        trigger.track();
+    }
+
+    /// Notifies the subscribers of every key matching `filter`, running
+    /// effects once all of them have been notified.
+    pub fn notify_where(&self, filter: impl Fn(&K) -> bool) {
+        let triggers = self
+            .triggers
+            .borrow()
+            .iter()
+            .filter(|(key, _)| filter(key))
+            .map(|(_, trigger)| *trigger)
+            .collect::<Vec<_>>();
+        batch(|| {
+            for trigger in triggers {
+                trigger.notify();
+            }
+        });
+    }
+
+    /// Disposes of the triggers of every key matching `filter` that nothing
+    /// is subscribed to, such as keys that have just been removed. They are
+    /// created again if the key is read later on.
+    pub fn release_where(&self, filter: impl Fn(&K) -> bool) {
+        _ = with_runtime(|runtime| {
+            self.triggers.borrow_mut().retain(|key, trigger| {
+                if !filter(key) || runtime.is_observed(trigger.id) {
+                    return true;
+                }
+                if let Some(owner) = self.owner {
+                    runtime.remove_scope_property(owner.0, ScopeProperty::Trigger(trigger.id));
+                }
+                runtime.dispose_node(trigger.id);
+                false
+            });
+        });
+    }
+}
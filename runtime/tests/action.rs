@@ -0,0 +1,112 @@
+use goober_runtime::{
+    create_action, create_isomorphic_effect, create_local_resource, create_multi_action,
+    provide_context, testing::run_test, use_query, QueryClient, SignalGet, SignalGetUntracked,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[test]
+fn dispatch_tracks_pending_input_and_value() {
+    run_test(|_| {
+        let double = create_action(|n: &i32| std::future::ready(n * 2));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let seen = Rc::clone(&seen);
+            move |_| {
+                seen.borrow_mut().push((
+                    double.pending().get(),
+                    double.input().get(),
+                    double.value().get(),
+                ))
+            }
+        });
+
+        double.dispatch(21);
+        assert_eq!(
+            *seen.borrow(),
+            [
+                (false, None, None),
+                (true, Some(21), None),
+                (false, None, Some(42))
+            ]
+        );
+        assert_eq!(double.version().get_untracked(), 1);
+
+        double.dispatch(1);
+        assert_eq!(double.value().get_untracked(), Some(2));
+        assert_eq!(double.version().get_untracked(), 2);
+    });
+}
+
+#[test]
+fn success_hooks_refresh_reads() {
+    run_test(|_| {
+        provide_context(QueryClient::new());
+
+        let fetches = Rc::new(Cell::new(0));
+        let fetch = {
+            let fetches = Rc::clone(&fetches);
+            move || {
+                fetches.set(fetches.get() + 1);
+                std::future::ready(fetches.get())
+            }
+        };
+        let todos = create_local_resource(|| (), {
+            let fetch = fetch.clone();
+            move |_| fetch()
+        });
+        let count = use_query(|| "todos", move |_| fetch());
+        assert_eq!(todos.get(), Some(1));
+        assert_eq!(count.get_untracked(), Some(2));
+
+        let save = create_action(|title: &&str| {
+            std::future::ready(if title.is_empty() {
+                Err("empty title")
+            } else {
+                Ok(())
+            })
+        });
+        save.on_success(move |_| todos.refetch());
+        save.invalidate_on_success("todos");
+
+        save.dispatch("");
+        assert_eq!(save.value().get_untracked(), Some(Err("empty title")));
+        assert_eq!(fetches.get(), 2);
+
+        save.dispatch("write tests");
+        assert_eq!(todos.get(), Some(3));
+        assert_eq!(count.get_untracked(), Some(4));
+    });
+}
+
+#[test]
+fn multi_actions_keep_every_submission() {
+    run_test(|_| {
+        let resolved = Rc::new(RefCell::new(Vec::new()));
+        let add = create_multi_action(|n: &i32| std::future::ready(n + 1));
+        add.on_resolved({
+            let resolved = Rc::clone(&resolved);
+            move |n| resolved.borrow_mut().push(*n)
+        });
+
+        add.dispatch(1);
+        add.dispatch(10);
+
+        let submissions = add.submissions().get_untracked();
+        let values = submissions
+            .iter()
+            .map(|submission| submission.value.get_untracked())
+            .collect::<Vec<_>>();
+        assert_eq!(values, [Some(2), Some(11)]);
+        assert_eq!(*resolved.borrow(), [2, 11]);
+        assert_eq!(add.version().get_untracked(), 2);
+
+        // cancelling a call that has finished does nothing
+        submissions[0].cancel();
+        assert!(!submissions[0].canceled().get_untracked());
+        assert!(!submissions[0].pending().get_untracked());
+    });
+}
@@ -0,0 +1,92 @@
+use goober_runtime::{
+    create_async_memo, create_isomorphic_effect, create_signal, testing::run_test, SignalGet,
+    SignalGetUntracked, SignalSet,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[test]
+fn only_reads_before_the_first_await_are_tracked() {
+    run_test(|_| {
+        let (a, set_a) = create_signal(1);
+        let (b, set_b) = create_signal(10);
+        let runs = Rc::new(Cell::new(0));
+        let sum = create_async_memo({
+            let runs = Rc::clone(&runs);
+            move || {
+                runs.set(runs.get() + 1);
+                async move {
+                    let a = a.get();
+                    tokio::task::yield_now().await;
+                    a + b.get()
+                }
+            }
+        });
+        assert_eq!(sum.get_untracked(), Some(11));
+
+        set_b.set(20);
+        assert_eq!(runs.get(), 1);
+        assert_eq!(sum.get_untracked(), Some(11));
+
+        set_a.set(2);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(sum.get_untracked(), Some(22));
+    });
+}
+
+#[test]
+fn keeps_the_previous_value_while_loading() {
+    run_test(|_| {
+        let (n, set_n) = create_signal(1);
+        let double = create_async_memo(move || {
+            let n = n.get();
+            async move {
+                tokio::task::yield_now().await;
+                n * 2
+            }
+        });
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let seen = Rc::clone(&seen);
+            move |_| {
+                seen.borrow_mut()
+                    .push((double.loading().get(), double.get()))
+            }
+        });
+
+        set_n.set(2);
+        assert_eq!(
+            *seen.borrow(),
+            [(false, Some(2)), (true, Some(2)), (false, Some(4))]
+        );
+    });
+}
+
+#[test]
+fn equal_results_do_not_notify() {
+    run_test(|_| {
+        let (n, set_n) = create_signal(1);
+        let parity = create_async_memo(move || {
+            let n = n.get();
+            async move { n % 2 }
+        });
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect({
+            let runs = Rc::clone(&runs);
+            move |_| {
+                parity.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        set_n.set(3);
+        assert_eq!(runs.get(), 1);
+        set_n.set(4);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(parity.get_untracked(), Some(0));
+    });
+}
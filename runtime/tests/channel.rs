@@ -0,0 +1,90 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_memo, create_signal, create_signal_from_broadcast,
+    create_signal_from_mpsc, create_signal_from_watch, mirror_to_watch,
+    testing::{run_test, TestContext},
+    ChannelOptions, SignalGet, SignalSet,
+};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, watch};
+
+/// Runs the runtime's sync writes until `done` holds.
+fn flush_until(cx: &TestContext<'_>, done: impl Fn() -> bool) {
+    for _ in 0..500 {
+        cx.flush();
+        if done() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(2));
+    }
+    panic!("timed out waiting for the channel");
+}
+
+#[test]
+fn mpsc_keeps_the_latest_and_recent_items_until_closed() {
+    run_test(|cx| {
+        let (tx, rx) = mpsc::channel(2);
+        let items = create_signal_from_mpsc(rx, ChannelOptions { buffer: 3 });
+        assert_eq!(items.get(), None);
+
+        std::thread::spawn(move || {
+            for n in 1..=5 {
+                tx.blocking_send(n).unwrap();
+            }
+        });
+        flush_until(cx, || items.closed().get());
+
+        assert_eq!(items.get(), Some(5));
+        assert_eq!(Vec::from(items.recent().get()), vec![3, 4, 5]);
+        assert_eq!(items.lagged().get(), 0);
+    });
+}
+
+#[test]
+fn broadcast_counts_lagged_items() {
+    run_test(|cx| {
+        let (tx, rx) = broadcast::channel(2);
+        for n in 1..=5 {
+            tx.send(n).unwrap();
+        }
+        drop(tx);
+
+        let items = create_signal_from_broadcast(rx, ChannelOptions::default());
+        flush_until(cx, || items.closed().get());
+
+        assert_eq!(items.get(), Some(5));
+        assert_eq!(items.lagged().get(), 3);
+        assert!(items.recent().get().is_empty());
+    });
+}
+
+#[test]
+fn watch_starts_with_the_current_value_and_stops_on_cleanup() {
+    run_test(|cx| {
+        let (tx, rx) = watch::channel("idle");
+        let (status, disposer) = as_child_of_current_owner(|rx| {
+            create_signal_from_watch(rx, ChannelOptions::default())
+        })(rx);
+        assert_eq!(status.get(), Some("idle"));
+
+        tx.send("loading").unwrap();
+        flush_until(cx, || status.get() == Some("loading"));
+
+        drop(disposer);
+        flush_until(cx, || tx.is_closed());
+    });
+}
+
+#[test]
+fn signals_are_mirrored_into_watch_senders() {
+    run_test(|_| {
+        let (count, set_count) = create_signal(2);
+        let squared = create_memo(move |_| count.get() * count.get());
+        let (tx, mut rx) = watch::channel(0);
+        mirror_to_watch(squared, tx);
+        assert_eq!(*rx.borrow_and_update(), 4);
+
+        set_count.set(3);
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(*rx.borrow_and_update(), 9);
+    });
+}
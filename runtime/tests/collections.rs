@@ -0,0 +1,214 @@
+use goober_runtime::{
+    create_isomorphic_effect, create_reactive_map, create_reactive_vec, create_runtime,
+    create_signal, MapDiff, SignalGet, SignalGetUntracked, SignalSet, SignalWithUntracked,
+    ValueKind, VecDiff,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+fn count_runs(f: impl Fn() + 'static) -> Rc<Cell<usize>> {
+    let runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect({
+        let runs = Rc::clone(&runs);
+        move |_| {
+            f();
+            runs.set(runs.get() + 1);
+        }
+    });
+    runs
+}
+
+#[test]
+fn vec_readers_only_wake_up_for_their_part() {
+    let runtime = create_runtime();
+
+    let items = create_reactive_vec(vec![1, 2, 3]);
+    let len_runs = count_runs(move || {
+        items.len();
+    });
+    let first_runs = count_runs(move || {
+        items.get(0);
+    });
+    let last_runs = count_runs(move || {
+        items.get(2);
+    });
+
+    items.set_item(1, 20);
+    assert_eq!(len_runs.get(), 1);
+    assert_eq!(first_runs.get(), 1);
+    assert_eq!(last_runs.get(), 1);
+
+    // everything after the removed item shifts
+    items.remove(1);
+    assert_eq!(len_runs.get(), 2);
+    assert_eq!(first_runs.get(), 1);
+    assert_eq!(last_runs.get(), 2);
+
+    items.move_item(1, 0);
+    assert_eq!(len_runs.get(), 2);
+    assert_eq!(first_runs.get(), 2);
+    assert_eq!(items.get_untracked(), [3, 1]);
+
+    items.set(vec![]);
+    assert_eq!(len_runs.get(), 3);
+    assert_eq!(first_runs.get(), 3);
+    assert!(items.is_empty());
+
+    runtime.dispose();
+}
+
+#[test]
+fn vec_diffs_can_be_replayed() {
+    let runtime = create_runtime();
+
+    let items = create_reactive_vec(vec!['a', 'b']);
+    let mirror = Rc::new(RefCell::new(items.get_untracked()));
+    items.on_diff({
+        let mirror = Rc::clone(&mirror);
+        move |diff| {
+            let mut mirror = mirror.borrow_mut();
+            match diff {
+                VecDiff::Insert { index, value } => mirror.insert(*index, **value),
+                VecDiff::Remove { index } => {
+                    mirror.remove(*index);
+                }
+                VecDiff::Update { index, value } => mirror[*index] = **value,
+                VecDiff::Move { from, to } => {
+                    let item = mirror.remove(*from);
+                    mirror.insert(*to, item);
+                }
+                VecDiff::Clear => mirror.clear(),
+                VecDiff::Replace { items } => *mirror = items.to_vec(),
+            }
+        }
+    });
+
+    items.push('c');
+    items.insert(0, 'z');
+    items.update_item(1, |item| *item = 'A');
+    items.move_item(0, 3);
+    assert_eq!(items.pop(), Some('z'));
+    items.remove(0);
+    assert_eq!(*mirror.borrow(), items.get_untracked());
+
+    items.clear();
+    items.set(vec!['x']);
+    assert_eq!(*mirror.borrow(), ['x']);
+
+    runtime.dispose();
+}
+
+#[test]
+fn map_readers_only_wake_up_for_their_key() {
+    let runtime = create_runtime();
+
+    let map = create_reactive_map([("a", 1), ("b", 2)]);
+    let len_runs = count_runs(move || {
+        map.len();
+    });
+    let a_runs = count_runs(move || {
+        map.get(&"a");
+    });
+    let c_runs = count_runs(move || {
+        map.contains_key(&"c");
+    });
+
+    assert_eq!(map.update_entry(&"b", |b| *b += 1), Some(()));
+    assert_eq!(len_runs.get(), 1);
+    assert_eq!(a_runs.get(), 1);
+
+    assert_eq!(map.insert("a", 10), Some(1));
+    assert_eq!(len_runs.get(), 1);
+    assert_eq!(a_runs.get(), 2);
+    assert_eq!(c_runs.get(), 1);
+
+    assert_eq!(map.insert("c", 3), None);
+    assert_eq!(len_runs.get(), 2);
+    assert_eq!(c_runs.get(), 2);
+
+    assert_eq!(map.remove(&"a"), Some(10));
+    assert_eq!(a_runs.get(), 3);
+    assert_eq!(map.remove(&"a"), None);
+    assert_eq!(a_runs.get(), 3);
+
+    let keys = map.with_untracked(|map| map.keys().copied().collect::<Vec<_>>());
+    assert_eq!(keys, ["b", "c"]);
+
+    runtime.dispose();
+}
+
+#[test]
+fn map_diffs_stop_with_their_owner() {
+    let runtime = create_runtime();
+
+    let map = create_reactive_map::<&str, i32>([]);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    let (_, disposer) = goober_runtime::as_child_of_current_owner({
+        let seen = Rc::clone(&seen);
+        move |_| {
+            map.on_diff({
+                let seen = Rc::clone(&seen);
+                move |diff| {
+                    seen.borrow_mut().push(match diff {
+                        MapDiff::Insert { key, value } => format!("insert {key} {value}"),
+                        MapDiff::Update { key, value } => format!("update {key} {value}"),
+                        MapDiff::Remove { key } => format!("remove {key}"),
+                        MapDiff::Clear => "clear".into(),
+                        MapDiff::Replace { entries } => format!("replace {}", entries.len()),
+                    })
+                }
+            })
+        }
+    })(());
+
+    map.insert("a", 1);
+    map.insert("a", 2);
+    map.remove(&"a");
+    map.clear();
+    drop(disposer);
+    map.insert("b", 1);
+
+    assert_eq!(
+        *seen.borrow(),
+        ["insert a 1", "update a 2", "remove a", "clear"]
+    );
+
+    runtime.dispose();
+}
+
+#[test]
+fn removed_keys_release_their_triggers() {
+    let runtime = create_runtime();
+
+    let map = create_reactive_map::<u32, u32>([]);
+    let (current, set_current) = create_signal(0);
+    let selected = count_runs(move || {
+        map.get(&current.get());
+    });
+    let triggers = || {
+        runtime
+            .alive_values()
+            .iter()
+            .filter(|value| value.kind == ValueKind::Trigger)
+            .count()
+    };
+    let before = triggers();
+
+    for key in 1..100 {
+        map.insert(key, key);
+        set_current.set(key);
+        map.remove(&(key - 1));
+    }
+    assert_eq!(selected.get(), 100);
+    assert_eq!(triggers(), before);
+
+    // keys that are still read keep their trigger
+    map.remove(&99);
+    assert_eq!(selected.get(), 101);
+    map.insert(99, 1);
+    assert_eq!(selected.get(), 102);
+
+    runtime.dispose();
+}
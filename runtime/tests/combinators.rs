@@ -0,0 +1,80 @@
+use goober_runtime::{
+    create_rw_signal, create_signal, testing::run_test, SignalExt, SignalGet, SignalSet, SignalWith,
+};
+
+#[test]
+fn chained_adapters_run_dependents_once_per_change() {
+    run_test(|cx| {
+        let (width, set_width) = create_signal(2);
+        let (height, set_height) = create_signal(3);
+        let area = width.zip(height).map(|(width, height)| width * height);
+        let runs = cx.count_runs(move || {
+            area.track();
+        });
+
+        set_width.set(4);
+        assert_eq!(area.get(), 12);
+        runs.assert_runs(2);
+
+        // 6 × 2 is still 12
+        goober_runtime::batch(|| {
+            set_width.set(6);
+            set_height.set(2);
+        });
+        runs.assert_runs(2);
+    });
+}
+
+#[test]
+fn filter_holds_the_last_accepted_value() {
+    run_test(|cx| {
+        let input = create_rw_signal(String::new());
+        let valid = input.filter(|input| !input.is_empty());
+        let runs = cx.count_runs(move || {
+            valid.track();
+        });
+        assert_eq!(valid.get(), None);
+
+        input.set("goober".to_string());
+        input.set(String::new());
+        assert_eq!(valid.get(), Some("goober".to_string()));
+        runs.assert_runs(2);
+    });
+}
+
+#[test]
+fn distinct_by_only_changes_with_the_key() {
+    run_test(|cx| {
+        let (user, set_user) = create_signal((1, "online"));
+        let by_id = user.distinct_by(|(id, _)| *id);
+        let runs = cx.count_runs(move || {
+            by_id.track();
+        });
+
+        set_user.set((1, "away"));
+        assert_eq!(by_id.get(), (1, "online"));
+        set_user.set((2, "away"));
+        assert_eq!(by_id.get(), (2, "away"));
+        runs.assert_runs(2);
+    });
+}
+
+#[test]
+fn previous_and_changed_count_follow_changes() {
+    run_test(|_| {
+        let (count, set_count) = create_signal(0);
+        let previous = count.previous();
+        let changes = count.changed_count();
+        let total = count.fold(0, |total, n| total + n);
+
+        set_count.set(1);
+        set_count.set(1);
+        set_count.set(5);
+        assert_eq!(previous.get(), Some(1));
+        assert_eq!(total.get(), 7);
+        // setting a signal notifies its subscribers even if the value is
+        // the same
+        assert_eq!(changes.get(), 3);
+        assert_eq!(count.changed_count().get(), 0);
+    });
+}
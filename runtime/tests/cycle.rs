@@ -0,0 +1,135 @@
+use goober_runtime::{
+    create_isomorphic_effect, create_memo, create_runtime, create_signal, Memo, SignalGet,
+    SignalGetUntracked, SignalSet,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+#[should_panic(expected = "Cycle detected in the reactive graph")]
+fn memo_cycle_is_detected() {
+    let runtime = create_runtime();
+
+    let b_memo: Rc<Cell<Option<Memo<i32>>>> = Rc::new(Cell::new(None));
+
+    let a = create_memo({
+        let b_memo = Rc::clone(&b_memo);
+        move |_| b_memo.get().map(|b| b.get()).unwrap_or(0) + 1
+    });
+    let b = create_memo(move |_| a.get() + 1);
+    b_memo.set(Some(b));
+
+    a.get();
+
+    runtime.dispose();
+}
+
+#[test]
+fn cycle_error_names_every_node() {
+    let runtime = create_runtime();
+
+    let b_memo: Rc<Cell<Option<Memo<i32>>>> = Rc::new(Cell::new(None));
+
+    let a = create_memo({
+        let b_memo = Rc::clone(&b_memo);
+        move |_| b_memo.get().map(|b| b.get()).unwrap_or(0) + 1
+    });
+    let b = create_memo(move |_| a.get() + 1);
+    b_memo.set(Some(b));
+
+    let err = std::panic::catch_unwind(move || a.get()).unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+
+    // one line for each of the two memos, both defined in this file
+    assert_eq!(message.matches("tests/cycle.rs").count(), 2);
+
+    runtime.dispose();
+}
+
+#[test]
+#[should_panic(expected = "An effect was re-run more than 100 times")]
+fn mutually_dependent_effects_are_stopped() {
+    let runtime = create_runtime();
+
+    let (a, set_a) = create_signal(0);
+    let (b, set_b) = create_signal(0);
+
+    create_isomorphic_effect(move |_| set_b.set(a.get() + 1));
+    create_isomorphic_effect(move |_| set_a.set(b.get() + 1));
+
+    runtime.dispose();
+}
+
+#[test]
+#[should_panic(expected = "An effect was re-run more than 3 times")]
+fn effect_rerun_limit_is_configurable() {
+    let runtime = create_runtime();
+    runtime.set_max_effect_reruns(3);
+    assert_eq!(runtime.max_effect_reruns(), 3);
+
+    let (a, set_a) = create_signal(0);
+    let (b, set_b) = create_signal(0);
+
+    create_isomorphic_effect(move |_| set_b.set(a.get() + 1));
+    create_isomorphic_effect(move |_| set_a.set(b.get() + 1));
+
+    runtime.dispose();
+}
+
+#[test]
+fn effects_that_settle_are_rerun() {
+    let runtime = create_runtime();
+
+    let (a, set_a) = create_signal(0);
+    let (b, set_b) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+
+    // `b` follows `a`, but never goes above 5
+    create_isomorphic_effect(move |_| {
+        let next = a.get().min(5);
+        if b.get_untracked() != next {
+            set_b.set(next);
+        }
+    });
+
+    // `a` is pushed back down to wherever `b` ended up
+    create_isomorphic_effect({
+        let runs = Rc::clone(&runs);
+        move |_| {
+            runs.set(runs.get() + 1);
+            let b = b.get();
+            if a.get_untracked() != b {
+                set_a.set(b);
+            }
+        }
+    });
+
+    set_a.set(10);
+
+    assert_eq!(a.get_untracked(), 5);
+    assert_eq!(b.get_untracked(), 5);
+    assert!(runs.get() < 10);
+
+    runtime.dispose();
+}
+
+#[test]
+fn panicking_memos_are_not_reported_as_cycles() {
+    let runtime = create_runtime();
+
+    let (fail, set_fail) = create_signal(true);
+    let memo = create_memo(move |_| {
+        if fail.get() {
+            panic!("failed to compute");
+        }
+        1
+    });
+    let doubled = create_memo(move |_| memo.get() * 2);
+
+    let err = std::panic::catch_unwind(move || doubled.get()).unwrap_err();
+    assert_eq!(err.downcast_ref::<&str>(), Some(&"failed to compute"));
+
+    set_fail.set(false);
+    assert_eq!(doubled.get(), 2);
+
+    runtime.dispose();
+}
@@ -0,0 +1,199 @@
+use goober_runtime::{
+    batch, create_effect_with_options, create_render_effect, create_signal, testing::run_test,
+    EffectOptions, EffectPhase, SignalGet, SignalSet,
+};
+use std::{cell::RefCell, rc::Rc};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+fn logged(log: &Log, name: &'static str, options: EffectOptions, read: impl Fn() + 'static) {
+    let log = Rc::clone(log);
+    create_effect_with_options(
+        move |_| {
+            read();
+            log.borrow_mut().push(name);
+        },
+        options,
+    );
+}
+
+#[test]
+fn effects_run_by_phase_and_then_by_priority() {
+    run_test(|_| {
+        let log = Log::default();
+        let (count, set_count) = create_signal(0);
+        let read = move || {
+            count.get();
+        };
+        logged(&log, "after", EffectOptions::default(), read);
+        logged(
+            &log,
+            "layout",
+            EffectOptions {
+                phase: EffectPhase::Layout,
+                priority: 0,
+            },
+            read,
+        );
+        logged(
+            &log,
+            "urgent layout",
+            EffectOptions {
+                phase: EffectPhase::Layout,
+                priority: 10,
+            },
+            read,
+        );
+        logged(
+            &log,
+            "before",
+            EffectOptions {
+                phase: EffectPhase::PreLayout,
+                priority: 0,
+            },
+            read,
+        );
+        log.borrow_mut().clear();
+
+        set_count.set(1);
+        assert_eq!(
+            *log.borrow(),
+            ["before", "urgent layout", "layout", "after"]
+        );
+    });
+}
+
+#[test]
+fn later_phases_see_what_earlier_phases_did() {
+    run_test(|_| {
+        let (width, set_width) = create_signal(10);
+        let (laid_out, set_laid_out) = create_signal(0);
+        let drawn = Rc::new(RefCell::new(Vec::new()));
+        create_render_effect({
+            let drawn = Rc::clone(&drawn);
+            move |_| drawn.borrow_mut().push((width.get(), laid_out.get()))
+        });
+        create_effect_with_options(
+            move |_| set_laid_out.set(width.get() * 2),
+            EffectOptions {
+                phase: EffectPhase::Layout,
+                priority: 0,
+            },
+        );
+        drawn.borrow_mut().clear();
+
+        // the render effect is marked dirty first, but only runs once layout
+        // has caught up
+        batch(|| set_width.set(30));
+        assert_eq!(*drawn.borrow(), [(30, 60)]);
+    });
+}
+
+#[test]
+fn deferred_phases_wait_for_the_frame() {
+    run_test(|cx| {
+        let log = Log::default();
+        let (count, set_count) = create_signal(0);
+        let read = move || {
+            count.get();
+        };
+        logged(
+            &log,
+            "layout",
+            EffectOptions {
+                phase: EffectPhase::Layout,
+                priority: 0,
+            },
+            read,
+        );
+        logged(
+            &log,
+            "render",
+            EffectOptions {
+                phase: EffectPhase::Render,
+                priority: 0,
+            },
+            read,
+        );
+        log.borrow_mut().clear();
+
+        cx.runtime().set_phase_deferred(EffectPhase::Layout, true);
+        cx.runtime().set_phase_deferred(EffectPhase::Render, true);
+        set_count.set(1);
+        set_count.set(2);
+        assert!(log.borrow().is_empty());
+
+        cx.runtime().run_phase(EffectPhase::Layout);
+        assert_eq!(*log.borrow(), ["layout"]);
+        cx.runtime().run_phase(EffectPhase::Render);
+        assert_eq!(*log.borrow(), ["layout", "render"]);
+    });
+}
+
+#[test]
+fn effects_written_to_by_a_phase_wait_for_it_to_finish() {
+    run_test(|_| {
+        let log = Log::default();
+        let (count, set_count) = create_signal(0);
+        let (other, set_other) = create_signal(0);
+        logged(
+            &log,
+            "pre1",
+            EffectOptions {
+                phase: EffectPhase::PreLayout,
+                priority: 1,
+            },
+            move || set_other.set(count.get()),
+        );
+        logged(
+            &log,
+            "pre2",
+            EffectOptions {
+                phase: EffectPhase::PreLayout,
+                priority: 0,
+            },
+            move || {
+                count.get();
+            },
+        );
+        logged(&log, "post", EffectOptions::default(), move || {
+            count.get();
+            other.get();
+        });
+        log.borrow_mut().clear();
+
+        // writing `other` runs pending effects before `pre2` has had its turn,
+        // but `post` still waits for it
+        set_count.set(1);
+        assert_eq!(*log.borrow(), ["pre1", "pre2", "post"]);
+    });
+}
+
+#[test]
+fn running_a_deferred_phase_runs_what_it_wrote_to() {
+    run_test(|cx| {
+        let log = Log::default();
+        let (count, set_count) = create_signal(0);
+        let (drawn, set_drawn) = create_signal(0);
+        logged(
+            &log,
+            "render",
+            EffectOptions {
+                phase: EffectPhase::Render,
+                priority: 0,
+            },
+            move || set_drawn.set(count.get()),
+        );
+        logged(&log, "after", EffectOptions::default(), move || {
+            drawn.get();
+        });
+        log.borrow_mut().clear();
+
+        cx.runtime().set_phase_deferred(EffectPhase::Render, true);
+        set_count.set(1);
+        assert!(log.borrow().is_empty());
+
+        cx.runtime().run_phase(EffectPhase::Render);
+        assert_eq!(*log.borrow(), ["render", "after"]);
+    });
+}
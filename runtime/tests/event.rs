@@ -0,0 +1,77 @@
+use goober_runtime::{
+    as_child_of_current_owner, batch, create_event_channel, create_isomorphic_effect,
+    create_signal, provide_context, testing::run_test, use_context, EventEmitter, SignalGet,
+    SignalSet,
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn every_value_in_a_batch_reaches_every_listener() {
+    run_test(|_| {
+        let (emitter, subscriber) = create_event_channel::<u32>();
+        let first = Rc::new(RefCell::new(Vec::new()));
+        let second = Rc::new(RefCell::new(Vec::new()));
+        for seen in [&first, &second] {
+            let seen = Rc::clone(seen);
+            subscriber.subscribe(move |n| seen.borrow_mut().push(*n));
+        }
+
+        batch(|| {
+            emitter.emit(1);
+            emitter.emit(1);
+            emitter.emit(2);
+        });
+        assert_eq!(*first.borrow(), [1, 1, 2]);
+        assert_eq!(*second.borrow(), [1, 1, 2]);
+    });
+}
+
+#[test]
+fn listeners_stop_when_their_owner_is_cleaned_up() {
+    run_test(|_| {
+        let (emitter, subscriber) = create_event_channel::<&str>();
+        provide_context(emitter);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let ((), disposer) = as_child_of_current_owner({
+            let seen = Rc::clone(&seen);
+            move |_| {
+                let seen = Rc::clone(&seen);
+                subscriber.subscribe(move |event| seen.borrow_mut().push(*event));
+            }
+        })(());
+
+        let emitter = use_context::<EventEmitter<&str>>().unwrap();
+        emitter.emit("scroll to top");
+        drop(disposer);
+        emitter.emit("show toast");
+        assert_eq!(*seen.borrow(), ["scroll to top"]);
+    });
+}
+
+#[test]
+fn emitting_from_an_effect_does_not_track_listeners() {
+    run_test(|cx| {
+        let (emitter, subscriber) = create_event_channel::<u32>();
+        let (count, set_count) = create_signal(0);
+        let (other, set_other) = create_signal(0);
+        subscriber.subscribe(move |_| {
+            other.get();
+        });
+        let runs = cx.count_runs(move || emitter.emit(count.get()));
+
+        set_other.set(1);
+        runs.assert_runs(1);
+        set_count.set(1);
+        runs.assert_runs(2);
+
+        // a listener may write to signals that other effects read
+        let total = Rc::new(RefCell::new(0));
+        create_isomorphic_effect({
+            let total = Rc::clone(&total);
+            move |_| *total.borrow_mut() += other.get()
+        });
+        subscriber.subscribe(move |n| set_other.set(*n));
+        set_count.set(5);
+        assert_eq!(*total.borrow(), 6);
+    });
+}
@@ -0,0 +1,115 @@
+use goober_runtime::{
+    batch, create_history, create_isomorphic_effect, create_runtime, create_rw_signal,
+    create_store, HistoryOptions, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+#[test]
+fn batches_and_transactions_are_one_step() {
+    let runtime = create_runtime();
+    let x = create_rw_signal(0);
+    let y = create_rw_signal(0);
+    let history = create_history(x, HistoryOptions::default());
+    history.track(y);
+
+    batch(|| {
+        x.set(1);
+        y.set(1);
+    });
+    history.transaction(|| {
+        x.set(2);
+        history.transaction(|| y.set(2));
+    });
+    y.set(3);
+
+    history.undo();
+    assert_eq!((x.get(), y.get()), (2, 2));
+    history.undo();
+    assert_eq!((x.get(), y.get()), (1, 1));
+    history.undo();
+    assert_eq!((x.get(), y.get()), (0, 0));
+    assert!(!history.can_undo().get());
+
+    history.redo();
+    assert_eq!((x.get(), y.get()), (1, 1));
+
+    // a new change drops what could be redone
+    x.set(10);
+    assert!(!history.can_redo().get());
+    history.undo();
+    assert_eq!((x.get(), y.get()), (1, 1));
+    runtime.dispose();
+}
+
+#[test]
+fn can_undo_and_redo_are_reactive() {
+    let runtime = create_runtime();
+    let n = create_rw_signal(0);
+    let history = create_history(n, HistoryOptions::default());
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    create_isomorphic_effect({
+        let seen = Rc::clone(&seen);
+        move |_| {
+            seen.borrow_mut()
+                .push((history.can_undo().get(), history.can_redo().get()))
+        }
+    });
+
+    n.set(1);
+    history.undo();
+    history.redo();
+    assert_eq!(
+        *seen.borrow(),
+        [(false, false), (true, false), (false, true), (true, false)]
+    );
+    runtime.dispose();
+}
+
+#[test]
+fn capacity_and_coalescing() {
+    let runtime = create_runtime();
+    let n = create_rw_signal(0);
+    let history = create_history(
+        n,
+        HistoryOptions {
+            capacity: Some(2),
+            ..Default::default()
+        },
+    );
+    for i in 1..=5 {
+        n.set(i);
+    }
+    history.undo();
+    history.undo();
+    history.undo();
+    assert_eq!(n.get_untracked(), 3);
+
+    let text = create_rw_signal(String::new());
+    let history = create_history(
+        text,
+        HistoryOptions {
+            coalesce: Some(Duration::from_secs(60)),
+            ..Default::default()
+        },
+    );
+    for c in "word".chars() {
+        text.update(|text| text.push(c));
+    }
+    history.undo();
+    assert_eq!(text.get_untracked(), "");
+    runtime.dispose();
+}
+
+#[test]
+fn tracks_store_paths() {
+    let runtime = create_runtime();
+    let store = create_store((String::from("a"), 0));
+    let name = store.field("name", |s| &s.0, |s| &mut s.0);
+    let history = create_history(name, HistoryOptions::default());
+
+    name.set("b".to_string());
+    history.undo();
+    assert_eq!(name.get_untracked(), "a");
+    runtime.dispose();
+}
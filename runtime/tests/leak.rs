@@ -0,0 +1,120 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_memo, create_runtime, create_signal, store_value,
+    ReactiveSystemError, SignalGet, ValueKind,
+};
+
+#[test]
+fn runtime_lists_alive_values() {
+    let runtime = create_runtime();
+
+    let (a, _) = create_signal(0);
+    let _double = create_memo(move |_| a.get() * 2);
+    let _stored = store_value(String::new());
+
+    let values = runtime.alive_values();
+    let kinds = values.iter().map(|value| value.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [ValueKind::Signal, ValueKind::Memo, ValueKind::StoredValue]
+    );
+    for value in values {
+        assert!(value.owner.is_some());
+        assert!(value.defined_at.unwrap().file().ends_with("leak.rs"));
+    }
+
+    runtime.dispose();
+}
+
+#[test]
+fn owner_lists_values_of_its_children() {
+    let runtime = create_runtime();
+
+    let (owner, disposer) = as_child_of_current_owner(|_| {
+        let _ = create_signal(0);
+        let (_, disposer) = as_child_of_current_owner(|_| store_value(0))(());
+        std::mem::forget(disposer);
+        goober_runtime::Owner::current().unwrap()
+    })(());
+
+    let kinds = owner
+        .alive_values()
+        .iter()
+        .map(|value| value.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [ValueKind::Signal, ValueKind::Child, ValueKind::StoredValue]
+    );
+
+    drop(disposer);
+    assert!(owner.alive_values().is_empty());
+
+    runtime.dispose();
+}
+
+#[test]
+fn forgotten_disposers_are_reported_as_children() {
+    let runtime = create_runtime();
+
+    let (_, disposer) = as_child_of_current_owner(|_| ())(());
+    std::mem::forget(disposer);
+
+    let leaked = runtime
+        .alive_values()
+        .into_iter()
+        .filter(|value| value.kind == ValueKind::Child)
+        .count();
+    assert_eq!(leaked, 1);
+
+    // disposing of the runtime warns about them once
+    runtime.dispose();
+    assert!(runtime
+        .alive_values()
+        .iter()
+        .all(|value| value.kind != ValueKind::Child));
+}
+
+#[test]
+fn disposed_signal_names_its_creation_site() {
+    let runtime = create_runtime();
+
+    let (signal, disposer) = as_child_of_current_owner(|_| create_signal(0).0)(());
+    assert!(signal.check_alive().is_ok());
+    drop(disposer);
+
+    match signal.check_alive() {
+        Err(ReactiveSystemError::Disposed {
+            kind, defined_at, ..
+        }) => {
+            assert_eq!(kind, ValueKind::Signal);
+            assert!(defined_at.unwrap().file().ends_with("leak.rs"));
+        }
+        other => panic!("expected a disposed error, got {other:?}"),
+    }
+
+    let err = std::panic::catch_unwind(move || signal.get()).unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("Attempted to access a signal after it was disposed."));
+    assert!(message.contains("signal created here"));
+
+    runtime.dispose();
+}
+
+#[test]
+#[should_panic(expected = "Attempted to access a stored value after it was disposed.")]
+fn disposed_stored_value_panics_with_creation_site() {
+    let runtime = create_runtime();
+
+    let value = store_value(0);
+    value.dispose();
+    assert!(matches!(
+        value.check_alive(),
+        Err(ReactiveSystemError::Disposed {
+            kind: ValueKind::StoredValue,
+            ..
+        })
+    ));
+    value.with_value(|_| {});
+
+    runtime.dispose();
+}
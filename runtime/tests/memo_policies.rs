@@ -0,0 +1,76 @@
+use goober_runtime::{
+    always_notify, create_lazy_memo, create_memo_with, create_rw_signal, create_signal,
+    testing::run_test, SignalGet, SignalSet, SignalUpdate, SignalWith,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn custom_equality_decides_when_dependents_run() {
+    run_test(|cx| {
+        let (reading, set_reading) = create_signal(f32::NAN);
+        // NaN != NaN, so a plain memo would notify on every NaN reading
+        let sensor = create_memo_with(
+            |a: &f32, b: &f32| a.to_bits() == b.to_bits(),
+            move |_| reading.get(),
+        );
+        let runs = cx.count_runs(move || {
+            sensor.track();
+        });
+
+        set_reading.set(f32::NAN);
+        runs.assert_runs(1);
+        set_reading.set(1.5);
+        runs.assert_runs(2);
+    });
+}
+
+#[test]
+fn always_notify_runs_dependents_on_every_recompute() {
+    run_test(|cx| {
+        let log = create_rw_signal(Vec::<u32>::new());
+        let len = create_memo_with(always_notify, move |_| log.with(Vec::len));
+        let runs = cx.count_runs(move || {
+            len.track();
+        });
+
+        log.update(|log| log.clear());
+        runs.assert_runs(2);
+    });
+}
+
+#[test]
+fn lazy_memos_are_released_without_subscribers() {
+    run_test(|cx| {
+        let computed = Rc::new(Cell::new(0));
+        let (count, set_count) = create_signal(1);
+        let double = create_lazy_memo({
+            let computed = Rc::clone(&computed);
+            move |_| {
+                computed.set(computed.get() + 1);
+                count.get() * 2
+            }
+        });
+        let quadruple = create_lazy_memo(move |_| double.get() * 2);
+        let (show, set_show) = create_signal(true);
+        let runs = cx.count_runs(move || {
+            if show.get() {
+                quadruple.track();
+            }
+        });
+
+        set_count.set(2);
+        assert_eq!(quadruple.get(), 8);
+        assert_eq!(computed.get(), 2);
+
+        // releasing `quadruple` leaves `double` without subscribers too
+        set_show.set(false);
+        runs.assert_runs(3);
+
+        // its value was dropped, so it runs again even though `count` hasn't
+        // changed since it was last read
+        set_show.set(true);
+        assert_eq!(computed.get(), 3);
+        assert_eq!(quadruple.get(), 8);
+        runs.assert_runs(4);
+    });
+}
@@ -0,0 +1,98 @@
+use goober_runtime::{
+    as_child_of_current_owner, batch, create_memo, create_signal, testing::run_test, watch,
+    with_owner, Disposer, Owner, SignalGet, SignalSet, SignalWith,
+};
+use std::{cell::Cell, rc::Rc};
+
+fn child_owner() -> (Owner, Disposer) {
+    as_child_of_current_owner(|_| Owner::current().unwrap())(())
+}
+
+#[test]
+fn paused_effects_run_once_on_resume() {
+    run_test(|cx| {
+        let (count, set_count) = create_signal(0);
+        let (tab, _disposer) = child_owner();
+        let runs = with_owner(tab, || {
+            cx.count_runs(move || {
+                count.track();
+            })
+        });
+
+        tab.pause();
+        assert!(tab.is_paused());
+        for n in 1..=5 {
+            set_count.set(n);
+        }
+        runs.assert_runs(1);
+
+        tab.resume();
+        assert!(!tab.is_paused());
+        runs.assert_runs(2);
+        set_count.set(6);
+        runs.assert_runs(3);
+    });
+}
+
+#[test]
+fn pausing_an_owner_pauses_its_descendants() {
+    run_test(|cx| {
+        let (count, set_count) = create_signal(0);
+        let (window, _window) = child_owner();
+        let (tab, _tab) = with_owner(window, child_owner);
+        let runs = with_owner(tab, || {
+            cx.count_runs(move || {
+                count.track();
+            })
+        });
+        let elsewhere = cx.count_runs(move || {
+            count.track();
+        });
+
+        window.pause();
+        tab.pause();
+        assert!(tab.is_paused());
+        set_count.set(1);
+        runs.assert_runs(1);
+        elsewhere.assert_runs(2);
+
+        // still paused by its own owner
+        window.resume();
+        set_count.set(2);
+        runs.assert_runs(1);
+
+        tab.resume();
+        runs.assert_runs(2);
+    });
+}
+
+#[test]
+fn resumed_effects_only_run_if_what_they_read_changed() {
+    run_test(|_| {
+        let (count, set_count) = create_signal(1);
+        let is_even = create_memo(move |_| count.get() % 2 == 0);
+        let calls = Rc::new(Cell::new(0));
+        let (tab, _disposer) = child_owner();
+        let _stop = with_owner(tab, || {
+            let calls = Rc::clone(&calls);
+            watch(
+                move || is_even.get(),
+                move |_, _, _| calls.set(calls.get() + 1),
+                false,
+            )
+        });
+
+        tab.pause();
+        batch(|| set_count.set(3));
+        set_count.set(5);
+        tab.resume();
+        assert_eq!(calls.get(), 0);
+
+        tab.pause();
+        set_count.set(6);
+        set_count.set(8);
+        assert_eq!(calls.get(), 0);
+        tab.resume();
+        assert_eq!(calls.get(), 1);
+    });
+}
@@ -0,0 +1,74 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_persisted_signal, create_runtime, provide_context,
+    DirectoryBackend, JsonFileBackend, PersistBackend, Persistence, SignalGet, SignalSet,
+};
+use std::{path::PathBuf, time::Duration};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("goober-{name}-{}", std::process::id()))
+}
+
+#[test]
+fn saves_once_the_debounce_time_has_passed() {
+    let path = temp_path("debounce.json");
+    let runtime = create_runtime();
+    provide_context(Persistence::new(JsonFileBackend::new(&path)).with_debounce(Duration::ZERO));
+
+    let width = create_persisted_signal("window.width", 800);
+    width.set(1024);
+    width.set(1280);
+    assert!(!path.exists());
+
+    runtime.run_timers();
+    assert_eq!(
+        JsonFileBackend::new(&path).load("window.width").unwrap(),
+        Some("1280".to_string())
+    );
+    runtime.dispose();
+
+    let runtime = create_runtime();
+    provide_context(Persistence::new(JsonFileBackend::new(&path)));
+    let width = create_persisted_signal("window.width", 800);
+    let height = create_persisted_signal("window.height", 600);
+    assert_eq!((width.get(), height.get()), (1280, 600));
+    runtime.dispose();
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn falls_back_to_the_default_on_schema_errors() {
+    let dir = temp_path("schema");
+    let backend = DirectoryBackend::new(&dir);
+    backend.save("theme", "{\"old\": true}").unwrap();
+
+    let runtime = create_runtime();
+    provide_context(Persistence::new(backend));
+    let theme = create_persisted_signal("theme", "dark".to_string());
+    assert_eq!(theme.get(), "dark");
+    runtime.dispose();
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn pending_saves_are_flushed_on_cleanup() {
+    let dir = temp_path("cleanup");
+    let runtime = create_runtime();
+    provide_context(Persistence::new(DirectoryBackend::new(&dir)));
+
+    let (_, disposer) = as_child_of_current_owner(|_| {
+        let layout = create_persisted_signal("panes/left", vec![1, 2]);
+        layout.set(vec![3]);
+    })(());
+    assert_eq!(
+        DirectoryBackend::new(&dir).load("panes/left").unwrap(),
+        None
+    );
+
+    drop(disposer);
+    assert_eq!(
+        DirectoryBackend::new(&dir).load("panes/left").unwrap(),
+        Some("[3]".to_string())
+    );
+    runtime.dispose();
+    std::fs::remove_dir_all(dir).unwrap();
+}
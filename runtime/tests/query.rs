@@ -0,0 +1,214 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_isomorphic_effect, create_signal, provide_context,
+    testing::run_test, use_query, QueryClient, QueryOptions, SignalGet, SignalGetUntracked,
+    SignalSet, SuspenseContext,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
+
+/// A fetcher that counts how many times each id was fetched, and returns
+/// `"{id}:{count}"`.
+fn counting_fetcher() -> (
+    Rc<RefCell<HashMap<u32, usize>>>,
+    impl Fn((&'static str, u32)) -> std::future::Ready<String> + Clone,
+) {
+    let fetches = Rc::new(RefCell::new(HashMap::new()));
+    let fetcher = {
+        let fetches = Rc::clone(&fetches);
+        move |(_, id): (&'static str, u32)| {
+            let mut fetches = fetches.borrow_mut();
+            let count = fetches.entry(id).or_insert(0);
+            *count += 1;
+            std::future::ready(format!("{id}:{count}"))
+        }
+    };
+    (fetches, fetcher)
+}
+
+#[test]
+fn cached_values_are_served_while_revalidating() {
+    run_test(|_| {
+        provide_context(QueryClient::new());
+        let (fetches, fetcher) = counting_fetcher();
+
+        let a = use_query(|| ("users", 1), fetcher.clone());
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let seen = Rc::clone(&seen);
+            move |_| seen.borrow_mut().push(a.get())
+        });
+        assert_eq!(*seen.borrow(), [Some("1:1".to_string())]);
+
+        // the entry is stale right away, so a new query refetches it, but
+        // never goes back to `None`
+        let b = use_query(|| ("users", 1), fetcher);
+        assert_eq!(fetches.borrow()[&1], 2);
+        assert_eq!(b.get_untracked(), Some("1:2".to_string()));
+        assert_eq!(
+            *seen.borrow(),
+            [Some("1:1".to_string()), Some("1:2".to_string())]
+        );
+        assert!(!a.loading().get_untracked());
+    });
+}
+
+#[test]
+fn fresh_entries_are_shared() {
+    run_test(|cx| {
+        provide_context(QueryClient::with_options(QueryOptions {
+            stale_time: Duration::from_secs(60),
+            ..Default::default()
+        }));
+        let (fetches, fetcher) = counting_fetcher();
+
+        let (id, set_id) = create_signal(1);
+        let a = use_query(move || ("users", id.get()), fetcher.clone());
+        let b = use_query(|| ("users", 1), fetcher.clone());
+        assert_eq!(fetches.borrow()[&1], 1);
+        assert_eq!(a.get_untracked(), b.get_untracked());
+        assert!(!a.is_stale());
+
+        set_id.set(2);
+        assert_eq!(a.get_untracked(), Some("2:1".to_string()));
+        set_id.set(1);
+        assert_eq!(a.get_untracked(), Some("1:1".to_string()));
+        assert_eq!(fetches.borrow()[&1], 1);
+
+        a.refetch();
+        assert_eq!(b.get_untracked(), Some("1:2".to_string()));
+
+        // entries go stale on the runtime's clock
+        cx.advance(Duration::from_secs(59));
+        assert!(!a.is_stale());
+        cx.advance(Duration::from_secs(1));
+        assert!(a.is_stale());
+    });
+}
+
+#[test]
+fn invalidation_matches_key_prefixes() {
+    run_test(|_| {
+        provide_context(QueryClient::with_options(QueryOptions {
+            stale_time: Duration::from_secs(60),
+            ..Default::default()
+        }));
+        let client = goober_runtime::expect_context::<QueryClient>();
+        let (fetches, fetcher) = counting_fetcher();
+
+        let user = use_query(|| ("users", 1), fetcher.clone());
+        let post = use_query(|| ("posts", 2), fetcher.clone());
+        let (_, disposer) = as_child_of_current_owner({
+            let fetcher = fetcher.clone();
+            move |_| use_query(|| ("users", 3), fetcher.clone())
+        })(());
+        drop(disposer);
+
+        client.invalidate("users");
+        assert_eq!(user.get_untracked(), Some("1:2".to_string()));
+        assert_eq!(post.get_untracked(), Some("2:1".to_string()));
+        // unused entries wait until they are used again
+        assert_eq!(fetches.borrow()[&3], 1);
+        use_query(|| ("users", 3), fetcher);
+        assert_eq!(fetches.borrow()[&3], 2);
+
+        client.invalidate(("users", 1));
+        assert_eq!(user.get_untracked(), Some("1:3".to_string()));
+    });
+}
+
+#[test]
+fn unused_entries_are_collected() {
+    run_test(|_| {
+        let client = QueryClient::with_options(QueryOptions {
+            gc_time: Duration::ZERO,
+            ..Default::default()
+        });
+        provide_context(client.clone());
+        let (_, fetcher) = counting_fetcher();
+
+        let kept = use_query(|| ("users", 1), fetcher.clone());
+        let (dropped, disposer) =
+            as_child_of_current_owner(move |_| use_query(|| ("users", 2), fetcher.clone()))(());
+        assert_eq!(client.len(), 2);
+        assert_eq!(dropped.get_untracked(), Some("2:1".to_string()));
+
+        drop(disposer);
+        client.collect_garbage();
+        assert_eq!(client.len(), 1);
+        assert_eq!(kept.get_untracked(), Some("1:1".to_string()));
+    });
+}
+
+#[test]
+fn fetches_suspend_their_readers() {
+    run_test(|_| {
+        provide_context(QueryClient::new());
+        let (_, fetcher) = counting_fetcher();
+        let user = use_query(|| ("users", 1), fetcher);
+
+        let suspense = SuspenseContext::new();
+        provide_context(suspense);
+        let pending = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let pending = Rc::clone(&pending);
+            move |_| pending.borrow_mut().push(suspense.pending_resources.get())
+        });
+        create_isomorphic_effect(move |_| user.get());
+
+        user.refetch();
+        assert_eq!(*pending.borrow(), [0, 1, 0]);
+        assert_eq!(user.get_untracked(), Some("1:2".to_string()));
+    });
+}
+
+#[test]
+fn unused_entries_are_collected_on_the_runtime_timers() {
+    run_test(|cx| {
+        let client = QueryClient::with_options(QueryOptions {
+            gc_time: Duration::from_secs(60),
+            ..Default::default()
+        });
+        provide_context(client.clone());
+        let (_, fetcher) = counting_fetcher();
+
+        let (_, disposer) =
+            as_child_of_current_owner(move |_| use_query(|| ("users", 1), fetcher.clone()))(());
+        drop(disposer);
+        assert_eq!(client.len(), 1);
+
+        cx.advance(Duration::from_secs(59));
+        assert_eq!(client.len(), 1);
+        cx.advance(Duration::from_secs(1));
+        assert!(client.is_empty());
+        assert_eq!(cx.runtime().next_timer(), None);
+    });
+}
+
+#[test]
+fn entries_used_again_are_not_collected() {
+    run_test(|cx| {
+        let client = QueryClient::with_options(QueryOptions {
+            gc_time: Duration::from_secs(60),
+            ..Default::default()
+        });
+        provide_context(client.clone());
+        let (_, fetcher) = counting_fetcher();
+
+        let (_, disposer) = as_child_of_current_owner({
+            let fetcher = fetcher.clone();
+            move |_| use_query(|| ("users", 1), fetcher.clone())
+        })(());
+        drop(disposer);
+        cx.advance(Duration::from_secs(30));
+        let user = use_query(|| ("users", 1), fetcher);
+        assert_eq!(cx.runtime().next_timer(), None);
+
+        cx.advance(Duration::from_secs(60));
+        assert_eq!(client.len(), 1);
+        assert_eq!(user.get_untracked(), Some("1:2".to_string()));
+    });
+}
@@ -0,0 +1,89 @@
+use goober_runtime::{
+    create_isomorphic_effect, create_runtime, create_store, Reactive, SignalGet,
+    SignalGetUntracked, SignalSet, SignalWith,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[derive(Clone, Debug, Default, PartialEq, Reactive)]
+struct Settings {
+    volume: u8,
+    theme: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Reactive)]
+struct Labelled<T> {
+    label: String,
+    value: T,
+}
+
+#[test]
+fn signals_round_trip() {
+    let runtime = create_runtime();
+
+    let value = Settings {
+        volume: 3,
+        theme: "dark".into(),
+    };
+    let signals = SettingsSignals::from(value.clone());
+    assert_eq!(signals.snapshot(), value);
+
+    signals.volume.set(4);
+    assert_eq!(signals.snapshot().volume, 4);
+    assert_eq!(signals.theme.get_untracked(), "dark");
+
+    runtime.dispose();
+}
+
+#[test]
+fn set_all_notifies_once() {
+    let runtime = create_runtime();
+
+    let signals = Settings::default().into_signals();
+    let runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect({
+        let runs = Rc::clone(&runs);
+        move |_| {
+            signals.snapshot();
+            runs.set(runs.get() + 1);
+        }
+    });
+
+    signals.set_all(Settings {
+        volume: 1,
+        theme: "light".into(),
+    });
+    assert_eq!(runs.get(), 2);
+    assert_eq!(signals.theme.get(), "light");
+
+    runtime.dispose();
+}
+
+#[test]
+fn generic_structs_and_store_fields() {
+    let runtime = create_runtime();
+
+    let signals = Labelled {
+        label: "count".to_string(),
+        value: 1,
+    }
+    .into_signals();
+    signals.value.set(2);
+    assert_eq!(signals.snapshot().value, 2);
+
+    let store = create_store(signals.snapshot());
+    let fields = Labelled::fields(store);
+    let label_runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect({
+        let label_runs = Rc::clone(&label_runs);
+        move |_| {
+            fields.label.track();
+            label_runs.set(label_runs.get() + 1);
+        }
+    });
+
+    fields.value.set(5);
+    assert_eq!(label_runs.get(), 1);
+    assert_eq!(store.with(|labelled| labelled.value), 5);
+
+    runtime.dispose();
+}
@@ -0,0 +1,169 @@
+use goober_runtime::{
+    create_local_resource_with_options, create_signal, testing::run_test, ResourceError,
+    ResourceOptions, SignalGet, SignalSet, MAX_RETRY_BACKOFF,
+};
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+#[test]
+fn failed_retries_keep_the_last_error() {
+    run_test(|cx| {
+        let attempts = Rc::new(Cell::new(0));
+        let data = create_local_resource_with_options(
+            || (),
+            {
+                let attempts = Rc::clone(&attempts);
+                move |_| {
+                    attempts.set(attempts.get() + 1);
+                    std::future::ready(Err::<(), _>(attempts.get()))
+                }
+            },
+            ResourceOptions::default().retry(2, Duration::from_millis(1)),
+        );
+        assert_eq!(attempts.get(), 1);
+        assert!(data.loading().get());
+
+        // the backoff doubles after each retry
+        cx.advance(Duration::from_millis(1));
+        assert_eq!(attempts.get(), 2);
+        cx.advance(Duration::from_millis(1));
+        assert_eq!(attempts.get(), 2);
+        cx.advance(Duration::from_millis(1));
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(data.get(), Some(Err(3)));
+        assert_eq!(data.attempt().get(), 3);
+        assert_eq!(data.error().get(), Some(ResourceError::Failed));
+        assert!(!data.loading().get());
+    });
+}
+
+#[test]
+fn retry_backoff_stops_doubling_at_the_maximum() {
+    run_test(|cx| {
+        let attempts = Rc::new(Cell::new(0));
+        let data = create_local_resource_with_options(
+            || (),
+            {
+                let attempts = Rc::clone(&attempts);
+                move |_| {
+                    attempts.set(attempts.get() + 1);
+                    std::future::ready(Err::<(), _>(attempts.get()))
+                }
+            },
+            ResourceOptions::default().retry(40, Duration::from_millis(1)),
+        );
+
+        // the backoff has stopped doubling well before the 24th attempt
+        cx.advance(Duration::from_secs(10 * 60 * 60));
+        let attempt = attempts.get();
+        assert!(attempt > 24);
+        let next = cx.runtime().next_timer().unwrap();
+        cx.advance(next - cx.clock().now());
+        assert_eq!(attempts.get(), attempt + 1);
+        let next = cx.runtime().next_timer().unwrap();
+        assert_eq!(next - cx.clock().now(), MAX_RETRY_BACKOFF);
+
+        cx.advance(Duration::from_secs(24 * 60 * 60));
+        assert_eq!(attempts.get(), 41);
+        assert_eq!(data.get(), Some(Err(41)));
+        assert_eq!(data.error().get(), Some(ResourceError::Failed));
+        assert_eq!(cx.runtime().next_timer(), None);
+    });
+}
+
+#[test]
+fn timeouts_keep_the_previous_value() {
+    run_test(|cx| {
+        let clock = cx.clock().clone();
+        let (delay, set_delay) = create_signal(Duration::ZERO);
+        let data = create_local_resource_with_options(
+            move || delay.get(),
+            move |delay| {
+                let clock = clock.clone();
+                async move {
+                    // the fetch takes `delay` to finish
+                    clock.advance(delay);
+                    delay
+                }
+            },
+            ResourceOptions::default().timeout(Duration::from_millis(50)),
+        );
+        assert_eq!(data.get(), Some(Duration::ZERO));
+        assert_eq!(data.error().get(), None);
+
+        set_delay.set(Duration::from_millis(49));
+        assert_eq!(data.get(), Some(Duration::from_millis(49)));
+        assert_eq!(data.error().get(), None);
+
+        set_delay.set(Duration::from_secs(5));
+        assert_eq!(data.get(), Some(Duration::from_millis(49)));
+        assert_eq!(data.error().get(), Some(ResourceError::TimedOut));
+        assert!(!data.loading().get());
+        assert_eq!(cx.runtime().next_timer(), None);
+    });
+}
+
+#[test]
+fn timed_out_attempts_are_retried() {
+    run_test(|cx| {
+        let clock = cx.clock().clone();
+        let attempts = Rc::new(Cell::new(0));
+        let data = create_local_resource_with_options(
+            || (),
+            {
+                let attempts = Rc::clone(&attempts);
+                move |_| {
+                    attempts.set(attempts.get() + 1);
+                    let attempt = attempts.get();
+                    let clock = clock.clone();
+                    async move {
+                        // only the first attempt is slow, and it is given up on
+                        // before it finishes
+                        if attempt == 1 {
+                            clock.advance(Duration::from_millis(105));
+                        }
+                        Ok::<_, ()>(attempt)
+                    }
+                }
+            },
+            ResourceOptions::default()
+                .timeout(Duration::from_millis(100))
+                .retry(1, Duration::from_millis(10)),
+        );
+        assert_eq!(data.get(), None);
+        assert!(data.loading().get());
+
+        cx.advance(Duration::from_millis(4));
+        assert_eq!(attempts.get(), 1);
+        cx.advance(Duration::from_millis(1));
+        assert_eq!(data.get(), Some(Ok(2)));
+        assert_eq!(data.attempt().get(), 2);
+        assert_eq!(data.error().get(), None);
+    });
+}
+
+#[test]
+fn polling_refetches_on_the_runtime_timers() {
+    run_test(|cx| {
+        let fetches = Rc::new(Cell::new(0));
+        let data = create_local_resource_with_options(
+            || (),
+            {
+                let fetches = Rc::clone(&fetches);
+                move |_| {
+                    fetches.set(fetches.get() + 1);
+                    std::future::ready(fetches.get())
+                }
+            },
+            ResourceOptions::default().refetch_interval(Duration::from_secs(10)),
+        );
+        assert_eq!(data.get(), Some(1));
+
+        cx.advance(Duration::from_secs(9));
+        assert_eq!(data.get(), Some(1));
+        cx.advance(Duration::from_secs(1));
+        assert_eq!(data.get(), Some(2));
+        // the refetch scheduled the next poll
+        cx.advance(Duration::from_secs(20));
+        assert_eq!(data.get(), Some(4));
+    });
+}
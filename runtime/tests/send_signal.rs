@@ -0,0 +1,80 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_rw_signal, create_signal, create_sync_setter,
+    testing::run_test, SignalGet, SignalWith,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+#[test]
+fn writes_from_other_threads_are_applied_in_one_batch() {
+    run_test(|cx| {
+        let (count, set_count) = create_signal(0);
+        let sync_count = create_sync_setter(set_count);
+        let runs = cx.count_runs(move || {
+            count.track();
+        });
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                let sync_count = sync_count.clone();
+                scope.spawn(move || {
+                    for _ in 0..25 {
+                        sync_count.update(|count| *count += 1);
+                    }
+                });
+            }
+        });
+        assert_eq!(count.get(), 0);
+
+        assert_eq!(cx.runtime().run_sync_writes(), 100);
+        assert_eq!(count.get(), 100);
+        runs.assert_runs(2);
+        assert_eq!(cx.runtime().run_sync_writes(), 0);
+    });
+}
+
+#[test]
+fn wakes_the_runtime_once_for_every_drain() {
+    run_test(|cx| {
+        let wakes = Arc::new(AtomicUsize::new(0));
+        cx.runtime().set_sync_waker({
+            let wakes = Arc::clone(&wakes);
+            move || {
+                wakes.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        let status = create_rw_signal("idle");
+        let sync_status = create_sync_setter(status);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                sync_status.set("loading");
+                sync_status.set("done");
+            });
+        });
+        assert_eq!(wakes.load(Ordering::SeqCst), 1);
+
+        cx.flush();
+        assert_eq!(status.get(), "done");
+        sync_status.set("idle");
+        assert_eq!(wakes.load(Ordering::SeqCst), 2);
+    });
+}
+
+#[test]
+fn writes_to_disposed_signals_are_dropped() {
+    run_test(|cx| {
+        let (sync_name, disposer) = as_child_of_current_owner(|_| {
+            let (_, set_name) = create_signal(String::new());
+            create_sync_setter(set_name)
+        })(());
+        drop(disposer);
+
+        std::thread::scope(|scope| {
+            scope.spawn(|| sync_name.set("goober".to_string()));
+        });
+        assert_eq!(cx.runtime().run_sync_writes(), 1);
+    });
+}
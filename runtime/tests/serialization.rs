@@ -0,0 +1,48 @@
+use goober_runtime::{Codec, Json, Serializable, SerializationError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Todo {
+    id: u32,
+    title: String,
+    done: bool,
+}
+
+fn todo() -> Todo {
+    Todo {
+        id: 7,
+        title: "water the goober".to_string(),
+        done: false,
+    }
+}
+
+#[test]
+fn serializable_uses_the_default_format() {
+    let serialized = todo().ser().unwrap();
+    assert_eq!(serialized, Json::encode_string(&todo()).unwrap());
+    assert_eq!(Todo::de(&serialized).unwrap(), todo());
+}
+
+#[test]
+fn json_reports_invalid_input() {
+    let result: Result<Todo, _> = Json::decode_str("{\"id\": \"seven\"}");
+    assert!(matches!(result, Err(SerializationError::Deserialize(_))));
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_is_smaller_than_json() {
+    use goober_runtime::Postcard;
+
+    let bytes = Postcard::encode(&todo()).unwrap();
+    assert!(bytes.len() < Json::encode(&todo()).unwrap().len());
+    let decoded: Todo = Postcard::decode(&bytes).unwrap();
+    assert_eq!(decoded, todo());
+
+    // as a string, binary formats are encoded as base64
+    let serialized = Postcard::encode_string(&todo()).unwrap();
+    assert!(serialized.is_ascii());
+    let decoded: Todo = Postcard::decode_str(&serialized).unwrap();
+    assert_eq!(decoded, todo());
+    assert!(<Postcard as Codec<Todo>>::decode(&bytes[..3]).is_err());
+}
@@ -0,0 +1,74 @@
+#![cfg(unix)]
+
+use goober_runtime::{
+    create_resource, create_rw_signal, testing::run_test, Resource, RwSignal, SharedContext,
+    SignalGet, SignalSet,
+};
+use std::{io::Write, os::unix::net::UnixStream};
+
+/// The state both processes create, in the same order.
+fn app(square: fn(u32) -> u32) -> (RwSignal<u32>, Resource<u32, u32>) {
+    let count = create_rw_signal(2);
+    let squared = create_resource(move || count.get(), move |n| async move { square(n) });
+    (count, squared)
+}
+
+fn in_ui(_: u32) -> u32 {
+    panic!("the resource was run in the UI process")
+}
+
+#[test]
+fn ui_resolves_resources_from_the_backend() {
+    let (backend, ui) = UnixStream::pair().unwrap();
+
+    run_test(move |_| {
+        let (count, _) = app(|n| n * n);
+        SharedContext::stream_resources(backend).unwrap();
+        count.set(3);
+    });
+
+    run_test(move |_| {
+        let receiver = SharedContext::receive_resources(ui, || {}).unwrap();
+        let (_, squared) = app(in_ui);
+        assert_eq!(squared.get(), None);
+        assert!(squared.loading().get());
+
+        while receiver.wait() {}
+        assert_eq!(squared.get(), Some(9));
+        assert!(!squared.loading().get());
+    });
+}
+
+#[test]
+fn values_received_before_a_resource_is_created_are_kept() {
+    let (backend, ui) = UnixStream::pair().unwrap();
+
+    run_test(move |_| {
+        app(|n| n * n);
+        SharedContext::stream_resources(backend).unwrap();
+    });
+
+    run_test(move |_| {
+        let receiver = SharedContext::receive_resources(ui, || {}).unwrap();
+        while receiver.wait() {}
+
+        let (_, squared) = app(in_ui);
+        assert_eq!(squared.get(), Some(4));
+
+        // resources the backend doesn't know about run in the UI
+        let local = create_resource(|| (), |_| async { 7 });
+        assert_eq!(local.get(), Some(7));
+    });
+}
+
+#[test]
+fn rejects_streams_without_a_list_of_resources() {
+    let (mut backend, ui) = UnixStream::pair().unwrap();
+    backend.write_all(b"{\"nonsense\": true}\n").unwrap();
+    drop(backend);
+
+    run_test(move |_| {
+        let error = SharedContext::receive_resources(ui, || {}).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    });
+}
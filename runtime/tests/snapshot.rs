@@ -0,0 +1,77 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_rw_signal, register_signal, testing::run_test, SignalGet,
+    SignalSet, SignalUpdate, Snapshot,
+};
+
+#[test]
+fn restores_into_a_fresh_runtime() {
+    let document = run_test(|cx| {
+        let width = create_rw_signal(800);
+        let title = create_rw_signal(String::new());
+        register_signal("window.width", width);
+        register_signal("window.title", title);
+
+        width.set(1024);
+        title.set("goober".to_string());
+        cx.runtime().snapshot().to_string()
+    });
+
+    run_test(|cx| {
+        let snapshot: Snapshot = document.parse().unwrap();
+        assert_eq!(snapshot.get("window.width"), Some("1024"));
+
+        let width = create_rw_signal(800);
+        let title = create_rw_signal(String::new());
+        register_signal("window.width", width);
+        register_signal("window.title", title);
+
+        // both signals change in one batch
+        let seen = create_rw_signal(Vec::<(i32, String)>::new());
+        let runs = cx.count_runs(move || {
+            let state = (width.get(), title.get());
+            seen.update(|seen| seen.push(state));
+        });
+        cx.runtime().restore(&snapshot);
+        runs.assert_runs(2);
+        assert_eq!(
+            seen.get(),
+            [(800, String::new()), (1024, "goober".to_string())]
+        );
+    });
+}
+
+#[test]
+fn signals_registered_later_take_restored_values() {
+    run_test(|cx| {
+        let document = r#"{"sidebar.open":"false","theme":"42"}"#;
+        cx.runtime().restore(&document.parse().unwrap());
+
+        let open = create_rw_signal(true);
+        register_signal("sidebar.open", open);
+        assert!(!open.get());
+
+        // a value of the wrong type is skipped
+        let theme = create_rw_signal("light".to_string());
+        register_signal("theme", theme);
+        assert_eq!(theme.get(), "light");
+
+        // values no signal has taken yet are kept in new snapshots
+        cx.runtime().restore(&r#"{"later":"1"}"#.parse().unwrap());
+        let snapshot = cx.runtime().snapshot();
+        assert_eq!(snapshot.get("later"), Some("1"));
+        assert_eq!(snapshot.get("theme"), Some("\"light\""));
+    });
+}
+
+#[test]
+fn signals_are_unregistered_with_their_owner() {
+    run_test(|cx| {
+        let (_, disposer) = as_child_of_current_owner(|_| {
+            register_signal("panel.height", create_rw_signal(300));
+        })(());
+        assert_eq!(cx.runtime().snapshot().get("panel.height"), Some("300"));
+
+        drop(disposer);
+        assert!(cx.runtime().snapshot().is_empty());
+    });
+}
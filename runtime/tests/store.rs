@@ -0,0 +1,146 @@
+use goober_runtime::{
+    create_isomorphic_effect, create_runtime, create_store, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, StoreField,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Profile {
+    bio: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct User {
+    name: String,
+    profile: Option<Profile>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct State {
+    user: User,
+    todos: Vec<String>,
+    count: usize,
+}
+
+fn count_runs<T, U>(field: StoreField<T, U>) -> Rc<Cell<usize>> {
+    let runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect({
+        let runs = Rc::clone(&runs);
+        move |_| {
+            field.track();
+            runs.set(runs.get() + 1);
+        }
+    });
+    runs
+}
+
+#[test]
+fn writes_only_notify_related_paths() {
+    let runtime = create_runtime();
+
+    let store = create_store(State::default());
+    let user = store.field("user", |s| &s.user, |s| &mut s.user);
+    let name = user.field("name", |u| &u.name, |u| &mut u.name);
+    let count = store.field("count", |s| &s.count, |s| &mut s.count);
+
+    let store_runs = count_runs(store);
+    let user_runs = count_runs(user);
+    let name_runs = count_runs(name);
+    let count_runs = count_runs(count);
+
+    // a leaf: its ancestors change too, its siblings don't
+    name.set("Alice".into());
+    assert_eq!(store_runs.get(), 2);
+    assert_eq!(user_runs.get(), 2);
+    assert_eq!(name_runs.get(), 2);
+    assert_eq!(count_runs.get(), 1);
+
+    // a parent: everything inside it may have changed
+    user.set(User::default());
+    assert_eq!(store_runs.get(), 3);
+    assert_eq!(user_runs.get(), 3);
+    assert_eq!(name_runs.get(), 3);
+    assert_eq!(count_runs.get(), 1);
+
+    count.update(|n| *n += 1);
+    assert_eq!(user_runs.get(), 3);
+    assert_eq!(name_runs.get(), 3);
+    assert_eq!(count_runs.get(), 2);
+    assert_eq!(store.get_untracked().count, 1);
+
+    runtime.dispose();
+}
+
+#[test]
+fn vec_items_are_tracked_by_index() {
+    let runtime = create_runtime();
+
+    let store = create_store(State {
+        todos: vec!["a".into(), "b".into()],
+        ..Default::default()
+    });
+    let todos = store.field("todos", |s| &s.todos, |s| &mut s.todos);
+    let first = todos.index(0);
+    let second = todos.index(1);
+    let third = todos.index(2);
+
+    let first_runs = count_runs(first);
+    let second_runs = count_runs(second);
+
+    second.set("c".into());
+    assert_eq!(first_runs.get(), 1);
+    assert_eq!(second_runs.get(), 2);
+    assert_eq!(todos.get_untracked(), ["a", "c"]);
+
+    assert_eq!(third.try_get(), None);
+    assert_eq!(third.try_set("d".into()), Some("d".into()));
+    todos.update(|todos| todos.push("d".into()));
+    assert_eq!(third.get(), "d");
+    assert_eq!(first_runs.get(), 2);
+
+    runtime.dispose();
+}
+
+#[test]
+fn options_can_be_unwrapped() {
+    let runtime = create_runtime();
+
+    let store = create_store(State::default());
+    let profile = store.field("user", |s| &s.user, |s| &mut s.user).field(
+        "profile",
+        |u| &u.profile,
+        |u| &mut u.profile,
+    );
+    let bio = profile.some().field("bio", |p| &p.bio, |p| &mut p.bio);
+
+    let seen = Rc::new(std::cell::RefCell::new(Vec::new()));
+    create_isomorphic_effect({
+        let seen = Rc::clone(&seen);
+        move |_| seen.borrow_mut().push(bio.try_get())
+    });
+
+    assert_eq!(bio.try_update(|bio| bio.push('!')), None);
+
+    profile.set(Some(Profile { bio: "hi".into() }));
+    bio.update(|bio| bio.push('!'));
+    profile.set(None);
+
+    assert_eq!(
+        *seen.borrow(),
+        [None, Some("hi".to_string()), Some("hi!".to_string()), None]
+    );
+    assert!(profile.with(Option::is_none));
+
+    runtime.dispose();
+}
+
+#[test]
+#[should_panic(expected = "Tried to access a path in a store that does not exist")]
+fn missing_paths_panic() {
+    let runtime = create_runtime();
+
+    let store = create_store(Vec::<i32>::new());
+    store.index(3).get();
+
+    runtime.dispose();
+}
@@ -0,0 +1,92 @@
+use goober_runtime::{
+    create_isomorphic_effect, create_signal, create_stream_resource, provide_context,
+    testing::run_test, SignalGet, SignalGetUntracked, SignalSet, SuspenseContext,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[test]
+fn restarts_when_the_source_changes() {
+    run_test(|_| {
+        let (prefix, set_prefix) = create_signal("a");
+        let items = create_stream_resource(
+            move || prefix.get(),
+            |prefix| futures::stream::iter((1..=3).map(move |n| format!("{prefix}{n}"))),
+        );
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let seen = Rc::clone(&seen);
+            move |_| seen.borrow_mut().push(items.get())
+        });
+        assert_eq!(*seen.borrow(), [Some("a3".to_string())]);
+        assert!(items.finished().get_untracked());
+        assert!(!items.loading().get_untracked());
+
+        set_prefix.set("b");
+        assert_eq!(
+            *seen.borrow(),
+            [
+                Some("a3".to_string()),
+                Some("b1".to_string()),
+                Some("b2".to_string()),
+                Some("b3".to_string())
+            ]
+        );
+    });
+}
+
+#[test]
+fn empty_streams_finish_without_a_value() {
+    run_test(|_| {
+        let items = create_stream_resource(|| (), |_| futures::stream::empty::<i32>());
+        assert_eq!(items.get_untracked(), None);
+        assert!(items.finished().get_untracked());
+        assert!(!items.loading().get_untracked());
+    });
+}
+
+#[test]
+fn suspends_until_the_first_item() {
+    run_test(|_| {
+        let (count, set_count) = create_signal(1);
+        let items =
+            create_stream_resource(move || count.get(), |count| futures::stream::iter(0..count));
+
+        let suspense = SuspenseContext::new();
+        provide_context(suspense);
+        let pending = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let pending = Rc::clone(&pending);
+            move |_| pending.borrow_mut().push(suspense.pending_resources.get())
+        });
+        create_isomorphic_effect(move |_| items.get());
+
+        // the restart suspends the reader until the new stream yields
+        set_count.set(2);
+        assert_eq!(*pending.borrow(), [0, 1, 0]);
+        assert_eq!(items.get_untracked(), Some(1));
+    });
+}
+
+#[test]
+fn signals_read_by_the_stream_are_not_tracked() {
+    run_test(|_| {
+        let (tick, set_tick) = create_signal(0);
+        let starts = Rc::new(Cell::new(0));
+        let items = create_stream_resource(|| (), {
+            let starts = Rc::clone(&starts);
+            move |_| {
+                starts.set(starts.get() + 1);
+                futures::stream::once(async move { tick.get() })
+            }
+        });
+        assert_eq!(items.get_untracked(), Some(0));
+
+        set_tick.set(1);
+        assert_eq!(starts.get(), 1);
+        assert_eq!(items.get_untracked(), Some(0));
+    });
+}
@@ -0,0 +1,63 @@
+use goober_runtime::{
+    batch, create_local_resource, create_rw_signal, create_signal, provide_context, set_timeout,
+    testing::run_test, use_context, SignalGet, SignalSet,
+};
+use std::time::Duration;
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+#[test]
+fn each_test_gets_its_own_runtime() {
+    run_test(|_| provide_context(42_u32));
+    run_test(|_| assert_eq!(use_context::<u32>(), None));
+}
+
+#[test]
+#[should_panic(expected = "inside the test")]
+fn panics_are_passed_on() {
+    run_test(|_| panic!("inside the test"));
+}
+
+#[test]
+fn counts_effect_runs() {
+    run_test(|cx| {
+        let (a, set_a) = create_signal(0);
+        let b = create_rw_signal(0);
+        let runs = cx.count_runs(move || {
+            a.get();
+            b.get();
+        });
+        runs.assert_runs(1);
+
+        batch(|| {
+            set_a.set(1);
+            b.set(1);
+        });
+        runs.assert_runs(2);
+
+        runs.reset();
+        set_a.set(2);
+        runs.assert_runs(1);
+    });
+}
+
+#[test]
+fn drives_futures_and_time() {
+    run_test(|cx| {
+        let resource = create_local_resource(
+            || (),
+            |_| async {
+                tokio::task::yield_now().await;
+                "loaded"
+            },
+        );
+        assert_eq!(resource.get(), Some("loaded"));
+
+        let (tx, rx) = futures::channel::oneshot::channel();
+        let start = cx.clock().now();
+        set_timeout(move || tx.send("an hour later").unwrap(), HOUR);
+        cx.advance(HOUR);
+        assert_eq!(cx.block_on(rx), Ok("an hour later"));
+        assert_eq!(cx.clock().now() - start, HOUR);
+    });
+}
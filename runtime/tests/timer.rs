@@ -0,0 +1,89 @@
+use goober_runtime::{
+    as_child_of_current_owner, create_interval, create_runtime, create_signal, now, set_timeout,
+    set_timeout_with_handle, throttled, SignalGet, SignalSet, TestClock,
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+const MS: Duration = Duration::from_millis(1);
+
+#[test]
+fn timeouts_are_cancelled_with_their_owner() {
+    let runtime = create_runtime();
+    let clock = TestClock::install();
+    let fired = Rc::new(RefCell::new(Vec::new()));
+    let fire = |name: &'static str| {
+        let fired = Rc::clone(&fired);
+        move || fired.borrow_mut().push(name)
+    };
+
+    set_timeout(fire("kept"), 10 * MS);
+    set_timeout_with_handle(fire("cancelled"), 10 * MS)
+        .unwrap()
+        .cancel();
+    let (_, disposer) = as_child_of_current_owner(|_| set_timeout(fire("disposed"), 10 * MS))(());
+    drop(disposer);
+
+    clock.advance(9 * MS);
+    assert!(fired.borrow().is_empty());
+    clock.advance(MS);
+    assert_eq!(*fired.borrow(), ["kept"]);
+    assert_eq!(runtime.next_timer(), None);
+    runtime.dispose();
+}
+
+#[test]
+fn intervals_tick_until_disposed() {
+    let runtime = create_runtime();
+    let clock = TestClock::install();
+    let (ticks, disposer) = as_child_of_current_owner(|_| create_interval(100 * MS))(());
+
+    clock.advance(250 * MS);
+    assert_eq!(ticks.get(), 2);
+    clock.advance(50 * MS);
+    assert_eq!(ticks.get(), 3);
+
+    drop(disposer);
+    assert_eq!(runtime.next_timer(), None);
+    runtime.dispose();
+}
+
+#[test]
+fn throttled_signals_pass_on_the_latest_value_per_window() {
+    let runtime = create_runtime();
+    let clock = TestClock::install();
+    let (position, set_position) = create_signal(0);
+    let throttled = throttled(position, 100 * MS);
+
+    set_position.set(1);
+    assert_eq!(throttled.get(), 1);
+    set_position.set(2);
+    set_position.set(3);
+    assert_eq!(throttled.get(), 1);
+
+    clock.advance(100 * MS);
+    assert_eq!(throttled.get(), 3);
+    // the window started again when 3 was passed on
+    set_position.set(4);
+    assert_eq!(throttled.get(), 3);
+    clock.advance(100 * MS);
+    assert_eq!(throttled.get(), 4);
+
+    // nothing was held back, so the next change goes through right away
+    clock.advance(100 * MS);
+    set_position.set(5);
+    assert_eq!(throttled.get(), 5);
+    runtime.dispose();
+}
+
+#[test]
+fn now_follows_the_clock() {
+    let runtime = create_runtime();
+    let clock = TestClock::install();
+    let start = clock.now();
+    let time = now(Duration::from_secs(1));
+    assert_eq!(time.get(), start);
+
+    clock.advance(Duration::from_millis(2500));
+    assert_eq!(time.get(), start + Duration::from_secs(2));
+    runtime.dispose();
+}
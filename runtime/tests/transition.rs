@@ -0,0 +1,66 @@
+use goober_runtime::{
+    create_isomorphic_effect, create_local_resource, create_signal, create_transition,
+    start_transition, testing::run_test, SignalGet, SignalSet,
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn held_values_commit_together() {
+    run_test(|_| {
+        let transition = create_transition();
+        let (tab, set_tab) = create_signal(1);
+        let title = create_local_resource(move || tab.get(), |tab| async move { tab * 10 });
+        let body = create_local_resource(move || tab.get(), |tab| async move { tab * 100 });
+        let page = transition.hold(move || (tab.get(), title.get(), body.get()));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let seen = Rc::clone(&seen);
+            move |_| seen.borrow_mut().push(page.get())
+        });
+        let pending = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect({
+            let pending = Rc::clone(&pending);
+            move |_| pending.borrow_mut().push(transition.is_pending().get())
+        });
+
+        start_transition(|| set_tab.set(2));
+        assert_eq!(
+            *seen.borrow(),
+            [(1, Some(10), Some(100)), (2, Some(20), Some(200))]
+        );
+        assert_eq!(*pending.borrow(), [false, true, false]);
+    });
+}
+
+#[test]
+fn holds_until_the_suspense_context_is_ready() {
+    run_test(|_| {
+        let transition = create_transition();
+        let (n, set_n) = create_signal(0);
+        let held = transition.hold(move || n.get());
+        assert_eq!(held.get(), 0);
+
+        start_transition(|| {
+            set_n.set(1);
+            transition.suspense().increment(false);
+            transition.suspense().increment(false);
+        });
+        transition.suspense().decrement(false);
+        assert!(transition.is_pending().get());
+        assert_eq!(held.get(), 0);
+
+        transition.suspense().decrement(false);
+        assert!(!transition.is_pending().get());
+        assert_eq!(held.get(), 1);
+    });
+}
+
+#[test]
+fn runs_directly_without_a_transition() {
+    run_test(|_| {
+        let (n, set_n) = create_signal(0);
+        start_transition(|| set_n.set(1));
+        assert_eq!(n.get(), 1);
+    });
+}